@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cue::Symbol;
+
+/// Set of the symbols a modality draws its cues from, e.g. 4 of the colors
+/// or the letters C, H, K and L.
+///
+/// Smaller sets make matches easier to spot, so the size of the set is a
+/// difficulty lever as much as N.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alphabet(u64);
+
+impl Alphabet {
+    /// Highest number of symbols an alphabet can hold.
+    pub const MAX_SYMBOLS: usize = u64::BITS as usize;
+
+    /// The `size` first symbols.
+    pub fn first(size: usize) -> Self {
+        Self::from_symbols(0..size)
+    }
+
+    /// The given symbols, ignoring those past [`Alphabet::MAX_SYMBOLS`].
+    pub fn from_symbols(symbols: impl IntoIterator<Item = Symbol>) -> Self {
+        Alphabet(
+            symbols
+                .into_iter()
+                .filter(|symbol| *symbol < Self::MAX_SYMBOLS)
+                .fold(0, |bits, symbol| bits | 1 << symbol),
+        )
+    }
+
+    pub fn contains(&self, symbol: Symbol) -> bool {
+        symbol < Self::MAX_SYMBOLS && self.0 & 1 << symbol != 0
+    }
+
+    /// Symbols of the set among the `symbols` first ones, in order.
+    pub fn symbols(&self, symbols: usize) -> Vec<Symbol> {
+        (0..symbols)
+            .filter(|symbol| self.contains(*symbol))
+            .collect()
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cue::{CueChain, Symbol};
+
+/// Number of distinct numbers shown in arithmetic games.
+pub const NUMBERS: usize = 9;
+
+/// The number shown for `symbol`, from 1 to `NUMBERS`.
+pub fn number(symbol: Symbol) -> i32 {
+    symbol as i32 + 1
+}
+
+/// Operation between the latest number and the one N back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Operation {
+    #[default]
+    Add,
+    Subtract,
+    Multiply,
+    /// Quotient rounded to the nearest whole number, so that any number can
+    /// follow the one N back.
+    Divide,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 4] = [
+        Operation::Add,
+        Operation::Subtract,
+        Operation::Multiply,
+        Operation::Divide,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Operation::Add => "Add",
+            Operation::Subtract => "Subtract",
+            Operation::Multiply => "Multiply",
+            Operation::Divide => "Divide",
+        }
+    }
+
+    pub fn sign(&self) -> &'static str {
+        match self {
+            Operation::Add => "+",
+            Operation::Subtract => "-",
+            Operation::Multiply => "x",
+            Operation::Divide => "/",
+        }
+    }
+
+    /// The operation following this one in `Operation::ALL`, wrapping around.
+    pub fn next(&self) -> Operation {
+        let i = Operation::ALL.iter().position(|op| op == self).unwrap_or(0);
+        Operation::ALL[(i + 1) % Operation::ALL.len()]
+    }
+
+    pub fn apply(&self, lhs: i32, rhs: i32) -> i32 {
+        match self {
+            Operation::Add => lhs + rhs,
+            Operation::Subtract => lhs - rhs,
+            Operation::Multiply => lhs * rhs,
+            Operation::Divide => (lhs as f32 / rhs as f32).round() as i32,
+        }
+    }
+
+    /// Which cues may follow the cue N back: a division never repeats it, so
+    /// that its results are not mostly 1.
+    pub fn rule(&self) -> fn(Symbol, Symbol) -> bool {
+        match self {
+            Operation::Divide => |n_back, cue| cue != n_back,
+            _ => |_, _| true,
+        }
+    }
+
+    /// Result expected for the latest cue of the chain, if there is a cue N
+    /// back to operate with.
+    pub fn result(&self, chain: &CueChain) -> Option<i32> {
+        let latest = chain.latest()?;
+        let n_back = chain.n_back_cue()?;
+        Some(self.apply(number(latest), number(n_back)))
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::VecDeque;
+
+use rand::{seq::SliceRandom, Rng};
+
+/// Number of matches placed in every block, as in the Jaeggi et al. (2008)
+/// protocol. Trials that are not assigned a match are non-matches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatchQuota {
+    /// Trials in which only that modality matches, for each modality.
+    pub single: usize,
+    /// Trials in which every modality matches at once.
+    pub shared: usize,
+}
+
+impl MatchQuota {
+    /// Six matches per modality, two of which are shared by all modalities.
+    pub const JAEGGI: MatchQuota = MatchQuota {
+        single: 4,
+        shared: 2,
+    };
+
+    /// Lay out which of the `total` trials are matches, for each modality
+    /// compared `n_backs[i]` cues back. The first N trials of a modality have
+    /// nothing to be compared with and are never matches. When the block is
+    /// too short to fit the whole quota, shared matches are placed first.
+    pub fn plan<R: Rng + ?Sized>(
+        &self,
+        total: usize,
+        n_backs: &[usize],
+        rng: &mut R,
+    ) -> Vec<VecDeque<bool>> {
+        let mut trials: Vec<usize> = (0..total).collect();
+        trials.shuffle(rng);
+
+        let mut plan = vec![VecDeque::from(vec![false; total]); n_backs.len()];
+
+        let max_n = n_backs.iter().copied().max().unwrap_or_default();
+        for trial in take_from(&mut trials, max_n, self.shared) {
+            for schedule in plan.iter_mut() {
+                schedule[trial] = true;
+            }
+        }
+
+        for (schedule, n) in plan.iter_mut().zip(n_backs) {
+            for trial in take_from(&mut trials, *n, self.single) {
+                schedule[trial] = true;
+            }
+        }
+
+        plan
+    }
+}
+
+/// Remove and return up to `count` trials not earlier than `first`.
+fn take_from(trials: &mut Vec<usize>, first: usize, count: usize) -> Vec<usize> {
+    let mut taken = Vec::with_capacity(count);
+    trials.retain(|trial| {
+        if taken.len() < count && *trial >= first {
+            taken.push(*trial);
+            false
+        } else {
+            true
+        }
+    });
+    taken
+}
@@ -0,0 +1,594 @@
+use std::collections::VecDeque;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{alphabet::Alphabet, block::MatchQuota, modality::ModalityId};
+
+/// Index of a cue among the cues a modality can present.
+pub type Symbol = usize;
+
+/// Settings of a single modality.
+#[derive(Clone, Copy, Debug)]
+pub struct CueSettings {
+    pub enabled: bool,
+    /// How many cues back a match is compared with.
+    pub n: usize,
+    /// Probability of a non-matching cue to be a lure.
+    pub lure_rate: f64,
+    /// Symbols the cues are drawn from, all of them when `None`.
+    pub alphabet: Option<Alphabet>,
+}
+
+impl CueSettings {
+    /// Lure rate of a modality once lures are on, among the ones offered by
+    /// the menu.
+    pub const LURE_RATE: f64 = 0.2;
+
+    /// Symbols the cues are drawn from among the `symbols` of the modality,
+    /// all of them if the alphabet holds none.
+    pub fn active_symbols(&self, symbols: usize) -> Vec<Symbol> {
+        match self.alphabet.map(|alphabet| alphabet.symbols(symbols)) {
+            Some(active) if !active.is_empty() => active,
+            _ => (0..symbols).collect(),
+        }
+    }
+}
+
+impl Default for CueSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            n: 2,
+            lure_rate: 0.0,
+            alphabet: None,
+        }
+    }
+}
+
+/// Relation of a cue with the ones shown before it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CueKind {
+    /// The cue repeats the one N back.
+    Match,
+    /// The cue repeats the one N-1 or N+1 back, but not the one N back.
+    Lure,
+    #[default]
+    Other,
+}
+
+/// Policy choosing how far back the cue compared with a new one is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TargetOffset {
+    /// Always N back.
+    #[default]
+    Fixed,
+    /// Crab-back: 1, 3, 5... up to 2N-1 back, then over again, e.g. 1, 3, 5,
+    /// 1, 3, 5... for 3-back.
+    Crab,
+}
+
+impl TargetOffset {
+    /// Distance of the target of the `trial`-th cue of an N-back chain.
+    pub fn offset(&self, n: usize, trial: usize) -> usize {
+        match self {
+            TargetOffset::Fixed => n,
+            TargetOffset::Crab => 1 + 2 * (trial % n),
+        }
+    }
+
+    /// Farthest distance of a target of an N-back chain.
+    pub fn max_offset(&self, n: usize) -> usize {
+        match self {
+            TargetOffset::Fixed => n,
+            TargetOffset::Crab => 2 * n - 1,
+        }
+    }
+}
+
+/// Memorization and generation of new cues.
+///
+/// Every chain owns its random number generator, so the same seed always
+/// yields the same sequence of cues.
+pub struct CueChain {
+    n: usize,
+    /// N the next cues are compared with, at most `n` in variable-N games.
+    current: usize,
+    target: TargetOffset,
+    /// Distance of the cue the latest one is compared with.
+    offset: usize,
+    /// Number of cues drawn so far.
+    trials: usize,
+    /// Number of distinct cues.
+    symbols: usize,
+    /// Symbols drawn uniformly, all of them unless restricted.
+    alphabet: Vec<Symbol>,
+    /// Latest cues up to one past the farthest target, so that lures past it
+    /// can be recalled.
+    short_memory: VecDeque<Option<Symbol>>,
+    rng: StdRng,
+    /// Planned match flag of each upcoming cue. Once exhausted, matches are
+    /// drawn at random.
+    schedule: VecDeque<bool>,
+    /// Cues to show next instead of drawing them.
+    script: VecDeque<Symbol>,
+    /// Probability of a non-matching cue to be a lure.
+    lure_rate: f64,
+    /// Whether a cue may follow the cue N back.
+    rule: fn(Symbol, Symbol) -> bool,
+}
+
+impl CueChain {
+    pub fn with_n_back(n: usize, symbols: usize, seed: u64) -> Self {
+        CueChain {
+            n,
+            current: n,
+            target: TargetOffset::Fixed,
+            offset: n,
+            trials: 0,
+            symbols,
+            alphabet: (0..symbols).collect(),
+            short_memory: VecDeque::from(vec![None; n + 2]),
+            rng: StdRng::seed_from_u64(seed),
+            schedule: VecDeque::new(),
+            script: VecDeque::new(),
+            lure_rate: 0.0,
+            rule: |_, _| true,
+        }
+    }
+
+    pub fn with_lure_rate(mut self, lure_rate: f64) -> Self {
+        self.lure_rate = lure_rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Only draw the given symbols, all of them if there are none.
+    pub fn with_alphabet(mut self, alphabet: Vec<Symbol>) -> Self {
+        if !alphabet.is_empty() {
+            self.alphabet = alphabet;
+        }
+        self
+    }
+
+    /// Only draw cues accepted by `rule` given the cue N back. Matches are
+    /// only drawn if the rule accepts the cue N back itself.
+    pub fn with_rule(mut self, rule: fn(Symbol, Symbol) -> bool) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Choose how far back the cue compared with a new one is.
+    pub fn with_target(mut self, target: TargetOffset) -> Self {
+        self.target = target;
+        self.short_memory = VecDeque::from(vec![None; self.max_offset() + 2]);
+        self
+    }
+
+    pub fn n_back(&self) -> usize {
+        self.n
+    }
+
+    pub fn lure_rate(&self) -> f64 {
+        self.lure_rate
+    }
+
+    /// Farthest distance of the cue compared with a new one, the first cue
+    /// that can match.
+    pub fn max_offset(&self) -> usize {
+        self.target.max_offset(self.n)
+    }
+
+    /// N the next cues are compared with.
+    pub fn current_n(&self) -> usize {
+        self.current
+    }
+
+    /// Compare the next cues with the one `n` back, from 1 up to N.
+    pub fn set_current_n(&mut self, n: usize) {
+        self.current = n.clamp(1, self.n);
+    }
+
+    /// Follow the given match flags for the next cues instead of drawing
+    /// matches at random.
+    pub fn set_schedule(&mut self, schedule: VecDeque<bool>) {
+        self.schedule = schedule;
+    }
+
+    /// Show the given cues next instead of drawing them.
+    pub fn set_script(&mut self, script: VecDeque<Symbol>) {
+        self.script = script;
+    }
+
+    /// Number of distinct cues.
+    pub fn symbols(&self) -> usize {
+        self.symbols
+    }
+
+    /// Symbols the cues are drawn from.
+    pub fn alphabet(&self) -> &[Symbol] {
+        &self.alphabet
+    }
+
+    pub fn target(&self) -> TargetOffset {
+        self.target
+    }
+
+    /// The latest cue, if any.
+    pub fn latest(&self) -> Option<Symbol> {
+        self.recall(0)
+    }
+
+    /// The cue the latest one is compared with, usually the one N back.
+    pub fn n_back_cue(&self) -> Option<Symbol> {
+        self.recall(self.offset)
+    }
+
+    /// Distance of the cue the latest one is compared with.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Distance of the cue the next one will be compared with.
+    fn next_offset(&self) -> usize {
+        self.target.offset(self.current, self.trials)
+    }
+
+    /// The cue shown `k` cues before the latest one, if any.
+    pub fn recall(&self, k: usize) -> Option<Symbol> {
+        self.short_memory
+            .len()
+            .checked_sub(k + 1)
+            .and_then(|i| self.short_memory[i])
+    }
+
+    /// Distances at which a repeated cue is a lure when compared `n` back.
+    fn lure_distances(n: usize) -> impl Iterator<Item = usize> {
+        [n - 1, n + 1].into_iter().filter(|k| *k > 0)
+    }
+
+    pub fn gen(&mut self) -> Symbol {
+        self.gen_excluding(&[])
+    }
+
+    /// Draw the next cue among the ones not in `excluded`, even if that
+    /// breaks a planned match, or outside the alphabet if it has none left.
+    /// At least one cue must be left to draw.
+    pub fn gen_excluding(&mut self, excluded: &[Symbol]) -> Symbol {
+        let offset = self.next_offset();
+        let cue = match self.script.pop_front() {
+            Some(cue) => cue,
+            None => self.draw(offset, excluded),
+        };
+
+        self.short_memory.push_back(Some(cue));
+        self.short_memory.pop_front();
+        self.offset = offset;
+        self.trials += 1;
+
+        cue
+    }
+
+    /// Draw a cue compared with the one `offset` back.
+    fn draw(&mut self, offset: usize, excluded: &[Symbol]) -> Symbol {
+        // once the new cue is pushed, the cue k back is currently k-1 back
+        let n_back = self.recall(offset - 1);
+        let rule = self.rule;
+        let accepts =
+            |cue: Symbol| !excluded.contains(&cue) && n_back.is_none_or(|n_back| rule(n_back, cue));
+        let lures: Vec<Symbol> = Self::lure_distances(offset)
+            .filter_map(|k| self.recall(k - 1))
+            .filter(|cue| Some(*cue) != n_back && accepts(*cue))
+            .collect();
+
+        let planned = self.schedule.pop_front();
+        let is_match = match planned {
+            Some(is_match) => is_match,
+            None => self.rng.gen::<f64>() < 0.25,
+        };
+
+        match n_back {
+            Some(n_back) if is_match && accepts(n_back) => n_back,
+            n_back => {
+                if !lures.is_empty() && self.rng.gen_bool(self.lure_rate) {
+                    lures[self.rng.gen_range(0..lures.len())]
+                } else {
+                    let mut cues: Vec<Symbol> = self
+                        .alphabet
+                        .iter()
+                        .copied()
+                        .filter(|c| accepts(*c))
+                        .collect();
+                    // the alphabet may be too small for distinct cues
+                    if cues.is_empty() {
+                        cues = (0..self.symbols).filter(|c| accepts(*c)).collect();
+                    }
+                    // a planned non-match avoids the cue N back whenever possible
+                    if planned.is_some() && cues.len() > 1 {
+                        cues.retain(|cue| Some(*cue) != n_back);
+                    }
+                    cues[self.rng.gen_range(0..cues.len())]
+                }
+            }
+        }
+    }
+
+    pub fn is_match(&self) -> bool {
+        self.kind() == CueKind::Match
+    }
+
+    pub fn is_lure(&self) -> bool {
+        self.kind() == CueKind::Lure
+    }
+
+    /// Relation of the latest cue with the previous ones.
+    pub fn kind(&self) -> CueKind {
+        self.kind_at(self.offset)
+    }
+
+    /// Relation of the latest cue with the previous ones, compared `n` back.
+    pub fn kind_at(&self, n: usize) -> CueKind {
+        self.kind_in(self, n)
+    }
+
+    /// Relation of the latest cue with the cues of another chain, compared
+    /// as far back as with the cues of this one.
+    pub fn kind_across(&self, other: &CueChain) -> CueKind {
+        self.kind_in(other, self.offset)
+    }
+
+    /// Relation of the latest cue with the cues of `history`, compared `n`
+    /// back.
+    fn kind_in(&self, history: &CueChain, n: usize) -> CueKind {
+        let Some(latest) = self.latest() else {
+            return CueKind::Other;
+        };
+
+        if history.recall(n) == Some(latest) {
+            CueKind::Match
+        } else if Self::lure_distances(n).any(|k| history.recall(k) == Some(latest)) {
+            CueKind::Lure
+        } else {
+            CueKind::Other
+        }
+    }
+}
+
+/// Comparison of the latest cue of a modality with the cue N back of
+/// another, e.g. of the letter heard with the letter seen N back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrossChannel {
+    /// Modality of the latest cue, whose N the comparison follows.
+    pub cue: ModalityId,
+    /// Modality of the cue N back.
+    pub target: ModalityId,
+}
+
+/// Cue chains of every enabled modality.
+pub struct CueEngine {
+    seed: u64,
+    rng: StdRng,
+    chains: Vec<(ModalityId, CueChain)>,
+    /// Modalities whose cues differ from each other in every round.
+    distinct: Vec<ModalityId>,
+    /// Channels comparing the cues of two modalities, scored like the
+    /// modalities themselves.
+    crosses: Vec<(ModalityId, CrossChannel)>,
+    /// Whether N is drawn anew for every cue, up to the lowest N of the
+    /// chains.
+    variable_n: bool,
+    /// N of the next cues of variable-N games, drawn once exhausted.
+    n_script: VecDeque<usize>,
+    /// N of the latest cue in variable-N games.
+    current_n: Option<usize>,
+    /// Lowest and highest N drawn so far in variable-N games.
+    n_range: Option<(usize, usize)>,
+}
+
+impl CueEngine {
+    /// Probability of a cue to be drawn as a match of a combination channel,
+    /// on top of the matches by chance.
+    pub const CROSS_MATCH_RATE: f64 = 0.125;
+
+    pub fn with_seed(seed: u64) -> Self {
+        CueEngine {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            chains: Vec::new(),
+            distinct: Vec::new(),
+            crosses: Vec::new(),
+            variable_n: false,
+            n_script: VecDeque::new(),
+            current_n: None,
+            n_range: None,
+        }
+    }
+
+    /// Add the chain of a modality presenting `symbols` distinct cues.
+    ///
+    /// The seed of the chain is derived from the seed of the engine and the
+    /// modality, so that enabling or disabling a modality does not change the
+    /// cues of the others.
+    pub fn with_chain(mut self, id: ModalityId, symbols: usize, settings: &CueSettings) -> Self {
+        let chain = CueChain::with_n_back(settings.n, symbols, self.seed ^ id.stable_hash())
+            .with_lure_rate(settings.lure_rate)
+            .with_alphabet(settings.active_symbols(symbols));
+        self.chains.push((id, chain));
+        self
+    }
+
+    /// Restrict the cues of the chain of a modality, see [`CueChain::with_rule`].
+    pub fn with_rule(mut self, id: ModalityId, rule: fn(Symbol, Symbol) -> bool) -> Self {
+        for (_, chain) in self.chains.iter_mut().filter(|(other, _)| *other == id) {
+            chain.rule = rule;
+        }
+        self
+    }
+
+    /// Draw different cues for the chains of the given modalities in every
+    /// round, e.g. distinct positions for several tiles. A planned match is
+    /// dropped when its cue is already taken.
+    pub fn with_distinct(mut self, ids: impl IntoIterator<Item = ModalityId>) -> Self {
+        self.distinct.extend(ids);
+        self
+    }
+
+    /// Add a channel comparing the cues of two chains, see [`CrossChannel`].
+    /// Its matches are drawn on top of the ones of the chains, unless their
+    /// cues are planned or scripted.
+    pub fn with_cross(mut self, id: ModalityId, channel: CrossChannel) -> Self {
+        self.crosses.push((id, channel));
+        self
+    }
+
+    /// Choose how far back the cue compared with a new one is, for every
+    /// chain.
+    pub fn with_target(mut self, target: TargetOffset) -> Self {
+        self.chains = self
+            .chains
+            .into_iter()
+            .map(|(id, chain)| (id, chain.with_target(target)))
+            .collect();
+        self
+    }
+
+    /// Draw the N of every cue between 1 and the lowest N of the chains, so
+    /// that every chain compares its cues with the same N.
+    pub fn with_variable_n(mut self) -> Self {
+        self.variable_n = true;
+        self
+    }
+
+    /// Show the given cues next on the chain of a modality instead of drawing
+    /// them.
+    pub fn with_script(mut self, id: ModalityId, script: VecDeque<Symbol>) -> Self {
+        for (_, chain) in self.chains.iter_mut().filter(|(other, _)| *other == id) {
+            chain.set_script(script.clone());
+        }
+        self
+    }
+
+    /// Compare the next cues with the given N instead of drawing it, see
+    /// [`CueEngine::with_variable_n`].
+    pub fn with_n_script(mut self, n_script: VecDeque<usize>) -> Self {
+        self.variable_n = true;
+        self.n_script = n_script;
+        self
+    }
+
+    /// Whether N is drawn anew for every cue.
+    pub fn is_variable_n(&self) -> bool {
+        self.variable_n
+    }
+
+    /// N of the latest cue in variable-N games.
+    pub fn current_n(&self) -> Option<usize> {
+        self.current_n
+    }
+
+    /// Lowest and highest N drawn so far in variable-N games.
+    pub fn n_range(&self) -> Option<(usize, usize)> {
+        self.n_range
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn chains(&self) -> impl Iterator<Item = (ModalityId, &CueChain)> {
+        self.chains.iter().map(|(id, chain)| (*id, chain))
+    }
+
+    pub fn chain(&self, id: ModalityId) -> Option<&CueChain> {
+        self.chains()
+            .find(|(other, _)| *other == id)
+            .map(|(_, chain)| chain)
+    }
+
+    pub fn crosses(&self) -> impl Iterator<Item = (ModalityId, CrossChannel)> + '_ {
+        self.crosses.iter().copied()
+    }
+
+    /// Relation of the latest cue of a combination channel with the cue it is
+    /// compared with.
+    pub fn cross_kind(&self, channel: CrossChannel) -> CueKind {
+        match (self.chain(channel.cue), self.chain(channel.target)) {
+            (Some(cue), Some(target)) => cue.kind_across(target),
+            _ => CueKind::Other,
+        }
+    }
+
+    /// N of every enabled modality.
+    pub fn levels(&self) -> Vec<(ModalityId, usize)> {
+        self.chains()
+            .map(|(id, chain)| (id, chain.n_back()))
+            .collect()
+    }
+
+    /// Plan the matches of a block of `total` cues so that every chain gets
+    /// exactly the matches of `quota`.
+    pub fn with_quota(mut self, quota: MatchQuota, total: usize) -> Self {
+        let n_backs: Vec<usize> = self.chains().map(|(_, c)| c.max_offset()).collect();
+        let plan = quota.plan(total, &n_backs, &mut self.rng);
+
+        for ((_, chain), schedule) in self.chains.iter_mut().zip(plan) {
+            chain.set_schedule(schedule);
+        }
+
+        self
+    }
+
+    /// Draw the next cue of every chain.
+    pub fn new_cue(&mut self) {
+        if self.variable_n {
+            let max_n = self.chains().map(|(_, c)| c.n_back()).min().unwrap_or(1);
+            let n = match self.n_script.pop_front() {
+                Some(n) => n.clamp(1, max_n),
+                None => self.rng.gen_range(1..=max_n),
+            };
+            for (_, chain) in self.chains.iter_mut() {
+                chain.set_current_n(n);
+            }
+            self.current_n = Some(n);
+            self.n_range = Some(match self.n_range {
+                Some((min, max)) => (min.min(n), max.max(n)),
+                None => (n, n),
+            });
+        }
+
+        for (_, channel) in self.crosses.iter() {
+            if !self.rng.gen_bool(Self::CROSS_MATCH_RATE) {
+                continue;
+            }
+            let Some(i) = self.chains.iter().position(|(id, _)| *id == channel.cue) else {
+                continue;
+            };
+            let cue = &self.chains[i].1;
+            if !cue.script.is_empty() || !cue.schedule.is_empty() {
+                continue;
+            }
+            // once the new cue is pushed, the cue k back is currently k-1 back
+            let offset = cue.next_offset();
+            let target = self
+                .chain(channel.target)
+                .and_then(|target| target.recall(offset - 1));
+            if let Some(target) = target {
+                self.chains[i].1.script.push_back(target);
+            }
+        }
+
+        let mut taken = Vec::new();
+        for (id, chain) in self.chains.iter_mut() {
+            if self.distinct.contains(id) {
+                taken.push(chain.gen_excluding(&taken));
+            } else {
+                chain.gen();
+            }
+        }
+    }
+}
+
+impl Default for CueEngine {
+    fn default() -> Self {
+        CueEngine::with_seed(rand::random())
+    }
+}
@@ -0,0 +1,77 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// How the blank interval between two trials varies, so that players cannot
+/// answer to the rhythm of the game rather than to the cues.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Jitter {
+    /// Every interval lasts exactly as long as set.
+    #[default]
+    None,
+    /// Uniform within the interval plus or minus this fraction of it.
+    Uniform(f32),
+    /// Exponential from half the interval, with the interval as mean before
+    /// it is cut at [`Jitter::EXPONENTIAL_MAX`] times the interval.
+    ///
+    /// The chance of the next trial starting stays the same the longer the
+    /// player waits, so not even the time waited gives it away.
+    Exponential,
+}
+
+impl Jitter {
+    pub const ALL: [Jitter; 4] = [
+        Jitter::None,
+        Jitter::Uniform(0.25),
+        Jitter::Uniform(0.5),
+        Jitter::Exponential,
+    ];
+
+    /// Longest exponential interval, as a multiple of the set interval.
+    pub const EXPONENTIAL_MAX: f32 = 3.0;
+
+    pub fn label(&self) -> String {
+        match self {
+            Jitter::None => "None".to_string(),
+            Jitter::Uniform(spread) => format!("±{}%", (spread * 100.0).round()),
+            Jitter::Exponential => "Exp".to_string(),
+        }
+    }
+
+    /// The jitter following this one in `Jitter::ALL`, wrapping around.
+    pub fn next(&self) -> Jitter {
+        let i = Jitter::ALL
+            .iter()
+            .position(|jitter| jitter == self)
+            .unwrap_or(0);
+        Jitter::ALL[(i + 1) % Jitter::ALL.len()]
+    }
+
+    /// Draw an interval around `interval` seconds, never negative.
+    pub fn sample(&self, interval: f32, rng: &mut impl Rng) -> f32 {
+        let interval = interval.max(0.0);
+        match *self {
+            Jitter::None => interval,
+            Jitter::Uniform(spread) => {
+                let spread = (spread * interval).abs();
+                if spread == 0.0 {
+                    interval
+                } else {
+                    rng.gen_range(interval - spread..=interval + spread)
+                        .max(0.0)
+                }
+            }
+            Jitter::Exponential => {
+                let min = interval / 2.0;
+                let scale = interval - min;
+                if scale <= 0.0 {
+                    return interval;
+                }
+                // inverse of the distribution function, truncated at the
+                // longest interval
+                let range = Self::EXPONENTIAL_MAX * interval - min;
+                let tail = 1.0 - (-range / scale).exp();
+                min - scale * (1.0 - rng.gen::<f32>() * tail).ln()
+            }
+        }
+    }
+}
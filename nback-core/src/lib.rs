@@ -0,0 +1,15 @@
+//! The n-back engine: cue generation, trials and scoring, free of any game
+//! framework so that it runs in tests, simulators and analysis tools alike.
+
+pub mod alphabet;
+pub mod arithmetic;
+pub mod block;
+pub mod cue;
+pub mod jitter;
+pub mod log;
+pub mod modality;
+pub mod plan;
+pub mod progression;
+pub mod round;
+pub mod score;
+pub mod session;
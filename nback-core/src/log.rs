@@ -0,0 +1,86 @@
+use std::{fmt, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::session::{Pause, Session, Task, TrialResult};
+
+/// Everything that happened in a game, trial by trial, for analysis outside
+/// of the game.
+///
+/// Logs are saved as RON, like plans.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionLog {
+    /// Seed the cues were drawn from.
+    pub seed: u64,
+    pub task: Task,
+    pub trials: Vec<TrialResult>,
+    pub pauses: Vec<Pause>,
+}
+
+#[derive(Debug)]
+pub enum LogError {
+    Io(io::Error),
+    Format(ron::Error),
+    Parse(ron::error::SpannedError),
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogError::Io(err) => write!(f, "cannot access the log: {}", err),
+            LogError::Format(err) => write!(f, "cannot write the log: {}", err),
+            LogError::Parse(err) => write!(f, "cannot read the log: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for LogError {}
+
+impl From<io::Error> for LogError {
+    fn from(err: io::Error) -> Self {
+        LogError::Io(err)
+    }
+}
+
+impl From<ron::Error> for LogError {
+    fn from(err: ron::Error) -> Self {
+        LogError::Format(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for LogError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        LogError::Parse(err)
+    }
+}
+
+impl SessionLog {
+    /// The trials scored so far in the session.
+    pub fn of(session: &Session) -> Self {
+        SessionLog {
+            seed: session.engine().seed(),
+            task: session.task(),
+            trials: session.trials().to_vec(),
+            pauses: session.pauses().to_vec(),
+        }
+    }
+
+    pub fn to_ron(&self) -> Result<String, LogError> {
+        let config = ron::ser::PrettyConfig::new()
+            .depth_limit(4)
+            .struct_names(true);
+        Ok(ron::ser::to_string_pretty(self, config)?)
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, LogError> {
+        Ok(ron::from_str(text)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), LogError> {
+        Ok(fs::write(path, self.to_ron()?)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LogError> {
+        Self::from_ron(&fs::read_to_string(path)?)
+    }
+}
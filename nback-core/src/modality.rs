@@ -0,0 +1,65 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{Mutex, OnceLock},
+};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Identifier of a modality, unique among the registered ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModalityId(pub &'static str);
+
+impl ModalityId {
+    /// FNV-1a hash of the identifier, stable across runs and platforms.
+    pub fn stable_hash(&self) -> u64 {
+        self.0.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    }
+
+    /// The identifier named `name`, kept for the rest of the program the
+    /// first time it is read so that reading it again costs nothing.
+    pub fn intern(name: &str) -> Self {
+        static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+        let mut names = NAMES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        match names.get(name) {
+            Some(name) => ModalityId(name),
+            None => {
+                let name: &'static str = Box::leak(name.into());
+                names.insert(name);
+                ModalityId(name)
+            }
+        }
+    }
+}
+
+impl Serialize for ModalityId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ModalityId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = ModalityId;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("the identifier of a modality")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<ModalityId, E> {
+                Ok(ModalityId::intern(name))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
@@ -0,0 +1,319 @@
+use std::{collections::VecDeque, fmt, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cue::{CrossChannel, CueEngine, CueSettings, Symbol, TargetOffset},
+    modality::ModalityId,
+    session::Task,
+};
+
+/// A whole game decided before it starts, cue by cue.
+///
+/// Plans are saved as RON, so that they can be read, edited or written by
+/// hand, and played again by anyone with the same outcome.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionPlan {
+    /// Seed the cues were drawn from, kept for reference.
+    pub seed: u64,
+    /// Duration of every trial, in seconds.
+    pub round_time: f32,
+    /// How long the cues of every trial are shown, in seconds, the whole
+    /// trial if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stimulus_time: Option<f32>,
+    pub task: Task,
+    #[serde(default)]
+    pub target: TargetOffset,
+    pub modalities: Vec<PlannedModality>,
+    /// Channels comparing the modalities with each other, in combination
+    /// games.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub crosses: Vec<PlannedCross>,
+    pub trials: Vec<PlannedTrial>,
+}
+
+/// A modality played in a plan.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlannedModality {
+    pub id: String,
+    pub n: usize,
+    /// Number of distinct cues.
+    pub symbols: usize,
+    /// Probability of a non-matching cue to be a lure, kept for reference
+    /// since the cues are planned.
+    #[serde(default)]
+    pub lure_rate: f64,
+}
+
+/// A channel comparing two modalities of a plan, see [`CrossChannel`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlannedCross {
+    pub id: String,
+    /// Modality of the latest cue.
+    pub cue: String,
+    /// Modality of the cue N back.
+    pub target: String,
+}
+
+/// The cues of a trial, in the order of the modalities of the plan.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlannedTrial {
+    pub cues: Vec<Symbol>,
+    /// Whether every cue matches the one N back, checked against the cues
+    /// when the plan is played.
+    pub matches: Vec<bool>,
+    /// N of the trial in variable-N games.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum PlanError {
+    Io(io::Error),
+    Format(ron::Error),
+    Parse(ron::error::SpannedError),
+    /// The plan names a modality that is not known.
+    UnknownModality(String),
+    /// The plan is inconsistent, e.g. a cue out of range.
+    Invalid(String),
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::Io(err) => write!(f, "cannot access the plan: {}", err),
+            PlanError::Format(err) => write!(f, "cannot write the plan: {}", err),
+            PlanError::Parse(err) => write!(f, "cannot read the plan: {}", err),
+            PlanError::UnknownModality(id) => write!(f, "unknown modality `{}`", id),
+            PlanError::Invalid(reason) => write!(f, "invalid plan: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+impl From<io::Error> for PlanError {
+    fn from(err: io::Error) -> Self {
+        PlanError::Io(err)
+    }
+}
+
+impl From<ron::Error> for PlanError {
+    fn from(err: ron::Error) -> Self {
+        PlanError::Format(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for PlanError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        PlanError::Parse(err)
+    }
+}
+
+impl SessionPlan {
+    /// Draw the `total` trials of a game from the engine.
+    pub fn record(mut engine: CueEngine, total: usize, round_time: f32, task: Task) -> Self {
+        let modalities = engine
+            .chains()
+            .map(|(id, chain)| PlannedModality {
+                id: id.0.to_string(),
+                n: chain.n_back(),
+                symbols: chain.symbols(),
+                lure_rate: chain.lure_rate(),
+            })
+            .collect();
+        let crosses = engine
+            .crosses()
+            .map(|(id, channel)| PlannedCross {
+                id: id.0.to_string(),
+                cue: channel.cue.0.to_string(),
+                target: channel.target.0.to_string(),
+            })
+            .collect();
+        let target = engine
+            .chains()
+            .next()
+            .map_or_else(TargetOffset::default, |(_, chain)| chain.target());
+
+        let trials = (0..total)
+            .map(|_| {
+                engine.new_cue();
+                PlannedTrial {
+                    cues: engine.chains().filter_map(|(_, c)| c.latest()).collect(),
+                    matches: engine.chains().map(|(_, c)| c.is_match()).collect(),
+                    n: engine.current_n(),
+                }
+            })
+            .collect();
+
+        SessionPlan {
+            seed: engine.seed(),
+            round_time,
+            stimulus_time: None,
+            task,
+            target,
+            modalities,
+            crosses,
+            trials,
+        }
+    }
+
+    pub fn to_ron(&self) -> Result<String, PlanError> {
+        let config = ron::ser::PrettyConfig::new()
+            .depth_limit(3)
+            .struct_names(true);
+        Ok(ron::ser::to_string_pretty(self, config)?)
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, PlanError> {
+        Ok(ron::from_str(text)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PlanError> {
+        Ok(fs::write(path, self.to_ron()?)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PlanError> {
+        Self::from_ron(&fs::read_to_string(path)?)
+    }
+
+    /// Whether any trial sets its own N.
+    pub fn is_variable_n(&self) -> bool {
+        self.trials.iter().any(|trial| trial.n.is_some())
+    }
+
+    /// An engine showing the cues of the plan, whose modalities are looked
+    /// up among the `registered` ones, with their number of distinct cues.
+    /// The plan is checked first: every modality must have as many symbols
+    /// as the registered one, and every trial a cue in range for every
+    /// modality and match flags agreeing with them.
+    pub fn engine(&self, registered: &[(ModalityId, usize)]) -> Result<CueEngine, PlanError> {
+        self.check_shape()?;
+
+        let mut engine = self.build(registered)?;
+        for (i, trial) in self.trials.iter().enumerate() {
+            engine.new_cue();
+            for ((id, chain), matches) in engine.chains().zip(&trial.matches) {
+                if chain.is_match() != *matches {
+                    return Err(PlanError::Invalid(format!(
+                        "the match flag of `{}` in trial {} disagrees with the cues",
+                        id.0, i
+                    )));
+                }
+            }
+        }
+
+        self.build(registered)
+    }
+
+    fn check_shape(&self) -> Result<(), PlanError> {
+        for modality in self.modalities.iter() {
+            if modality.n == 0 || modality.symbols == 0 {
+                return Err(PlanError::Invalid(format!(
+                    "`{}` needs an N and symbols of at least 1",
+                    modality.id
+                )));
+            }
+        }
+
+        for cross in self.crosses.iter() {
+            for name in [&cross.cue, &cross.target] {
+                if !self.modalities.iter().any(|modality| modality.id == *name) {
+                    return Err(PlanError::Invalid(format!(
+                        "`{}` compares `{}`, which is not played",
+                        cross.id, name
+                    )));
+                }
+            }
+        }
+
+        for (i, trial) in self.trials.iter().enumerate() {
+            if trial.cues.len() != self.modalities.len()
+                || trial.matches.len() != self.modalities.len()
+            {
+                return Err(PlanError::Invalid(format!(
+                    "trial {} needs a cue and a match flag for each of the {} modalities",
+                    i,
+                    self.modalities.len()
+                )));
+            }
+
+            for (cue, modality) in trial.cues.iter().zip(&self.modalities) {
+                if *cue >= modality.symbols {
+                    return Err(PlanError::Invalid(format!(
+                        "cue {} of `{}` in trial {} is out of range",
+                        cue, modality.id, i
+                    )));
+                }
+            }
+
+            if trial.n == Some(0) || (self.is_variable_n() && trial.n.is_none()) {
+                return Err(PlanError::Invalid(format!(
+                    "trial {} needs an N of at least 1, like every trial of a variable-N plan",
+                    i
+                )));
+            }
+
+            if let Some(modality) = self
+                .modalities
+                .iter()
+                .find(|modality| trial.n.is_some_and(|n| n > modality.n))
+            {
+                return Err(PlanError::Invalid(format!(
+                    "trial {} compares its cues further back than the N of `{}`",
+                    i, modality.id
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn build(&self, registered: &[(ModalityId, usize)]) -> Result<CueEngine, PlanError> {
+        let lookup = |name: &str| {
+            registered
+                .iter()
+                .find(|(id, _)| id.0 == name)
+                .copied()
+                .ok_or_else(|| PlanError::UnknownModality(name.to_string()))
+        };
+        let find = |name: &str| lookup(name).map(|(id, _)| id);
+
+        let mut engine = CueEngine::with_seed(self.seed);
+        for (i, modality) in self.modalities.iter().enumerate() {
+            let (id, symbols) = lookup(&modality.id)?;
+            if modality.symbols != symbols {
+                return Err(PlanError::Invalid(format!(
+                    "`{}` has {} symbols in the plan but {} in this game",
+                    modality.id, modality.symbols, symbols
+                )));
+            }
+            let settings = CueSettings {
+                n: modality.n,
+                lure_rate: modality.lure_rate,
+                ..CueSettings::default()
+            };
+            let script: VecDeque<Symbol> = self.trials.iter().map(|t| t.cues[i]).collect();
+
+            engine = engine
+                .with_chain(id, modality.symbols, &settings)
+                .with_script(id, script);
+        }
+
+        for cross in self.crosses.iter() {
+            let channel = CrossChannel {
+                cue: find(&cross.cue)?,
+                target: find(&cross.target)?,
+            };
+            engine = engine.with_cross(find(&cross.id)?, channel);
+        }
+
+        engine = engine.with_target(self.target);
+        if self.is_variable_n() {
+            engine = engine.with_n_script(self.trials.iter().filter_map(|t| t.n).collect());
+        }
+
+        Ok(engine)
+    }
+}
@@ -0,0 +1,212 @@
+use std::fmt;
+
+use crate::{modality::ModalityId, score::Score, session::Task};
+
+/// Way the N of the next game moves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+/// Why the N of the next game moves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reason {
+    /// The score reached the threshold to go up.
+    ScoreAbove { percent: usize, threshold: usize },
+    /// The score fell under the threshold for the given number of games in
+    /// a row.
+    Strikes { strikes: usize, threshold: usize },
+    /// Every modality had fewer errors than the limit to go up.
+    FewErrors { most: usize, limit: usize },
+    /// A modality had more errors than the limit to go down.
+    ManyErrors {
+        id: ModalityId,
+        errors: usize,
+        limit: usize,
+    },
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::ScoreAbove { percent, threshold } => {
+                write!(f, "{}%, at least {}%", percent, threshold)
+            }
+            Reason::Strikes { strikes, threshold } => {
+                write!(f, "{} games under {}%", strikes, threshold)
+            }
+            Reason::FewErrors { limit, .. } => {
+                write!(f, "under {} errors in every modality", limit)
+            }
+            Reason::ManyErrors { id, errors, limit } => {
+                write!(f, "{} errors in {}, over {}", errors, id.0, limit)
+            }
+        }
+    }
+}
+
+/// A move of the N decided after a game.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LevelChange {
+    pub direction: Direction,
+    pub reason: Reason,
+}
+
+/// Decides whether the N goes up or down after every game.
+///
+/// Rules may keep track of the previous games, so a rule lives as long as
+/// the player keeps it.
+pub trait ProgressionRule: Send + Sync {
+    /// The move of the N after a game of the task scored `score`, `None` to
+    /// keep it.
+    fn decide(&mut self, score: &Score, task: Task) -> Option<LevelChange>;
+}
+
+/// Brain Workshop's rule: up from 80%, down after three games in a row
+/// under 50%.
+#[derive(Clone, Debug, Default)]
+pub struct BrainWorkshop {
+    strikes: usize,
+}
+
+impl BrainWorkshop {
+    pub const UP_PERCENT: usize = 80;
+    pub const DOWN_PERCENT: usize = 50;
+    pub const STRIKES: usize = 3;
+
+    /// Games in a row under `BrainWorkshop::DOWN_PERCENT` so far.
+    pub fn strikes(&self) -> usize {
+        self.strikes
+    }
+}
+
+impl ProgressionRule for BrainWorkshop {
+    fn decide(&mut self, score: &Score, task: Task) -> Option<LevelChange> {
+        // the F1 score of matches, the accuracy of arithmetic results
+        let percent = match task {
+            Task::Match => score.f1_score_percent(),
+            Task::Arithmetic(_) => score.accuracy_percent(),
+        };
+
+        if percent >= Self::UP_PERCENT {
+            self.strikes = 0;
+            Some(LevelChange {
+                direction: Direction::Up,
+                reason: Reason::ScoreAbove {
+                    percent,
+                    threshold: Self::UP_PERCENT,
+                },
+            })
+        } else if percent < Self::DOWN_PERCENT {
+            self.strikes += 1;
+            if self.strikes < Self::STRIKES {
+                return None;
+            }
+            self.strikes = 0;
+            Some(LevelChange {
+                direction: Direction::Down,
+                reason: Reason::Strikes {
+                    strikes: Self::STRIKES,
+                    threshold: Self::DOWN_PERCENT,
+                },
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// The rule of Jaeggi et al. (2008): up with fewer than 3 errors in every
+/// modality, down with more than 5 in any.
+#[derive(Clone, Debug, Default)]
+pub struct Jaeggi;
+
+impl Jaeggi {
+    pub const UP_ERRORS: usize = 3;
+    pub const DOWN_ERRORS: usize = 5;
+}
+
+impl ProgressionRule for Jaeggi {
+    fn decide(&mut self, score: &Score, _task: Task) -> Option<LevelChange> {
+        let (id, errors) = score
+            .modalities()
+            .map(|(id, score)| (id, score.wrong()))
+            .max_by_key(|(_, errors)| *errors)?;
+
+        if errors > Self::DOWN_ERRORS {
+            Some(LevelChange {
+                direction: Direction::Down,
+                reason: Reason::ManyErrors {
+                    id,
+                    errors,
+                    limit: Self::DOWN_ERRORS,
+                },
+            })
+        } else if errors < Self::UP_ERRORS {
+            Some(LevelChange {
+                direction: Direction::Up,
+                reason: Reason::FewErrors {
+                    most: errors,
+                    limit: Self::UP_ERRORS,
+                },
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// The N only changes when the player changes it.
+#[derive(Clone, Debug, Default)]
+pub struct Manual;
+
+impl ProgressionRule for Manual {
+    fn decide(&mut self, _score: &Score, _task: Task) -> Option<LevelChange> {
+        None
+    }
+}
+
+/// Built-in progression rules, to choose from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Progression {
+    #[default]
+    BrainWorkshop,
+    Jaeggi,
+    Manual,
+}
+
+impl Progression {
+    pub const ALL: [Progression; 3] = [
+        Progression::BrainWorkshop,
+        Progression::Jaeggi,
+        Progression::Manual,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Progression::BrainWorkshop => "Brain Workshop",
+            Progression::Jaeggi => "Jaeggi",
+            Progression::Manual => "Manual",
+        }
+    }
+
+    /// The progression following this one in `Progression::ALL`, wrapping
+    /// around.
+    pub fn next(&self) -> Progression {
+        let i = Progression::ALL
+            .iter()
+            .position(|progression| progression == self)
+            .unwrap_or(0);
+        Progression::ALL[(i + 1) % Progression::ALL.len()]
+    }
+
+    /// A new rule of this progression, without any game behind it.
+    pub fn rule(&self) -> Box<dyn ProgressionRule> {
+        match self {
+            Progression::BrainWorkshop => Box::<BrainWorkshop>::default(),
+            Progression::Jaeggi => Box::new(Jaeggi),
+            Progression::Manual => Box::new(Manual),
+        }
+    }
+}
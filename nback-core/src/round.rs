@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use crate::modality::ModalityId;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Round {
+    pub total: usize,
+    pub current: usize,
+}
+
+impl Round {
+    pub fn with_total(total: usize) -> Self {
+        Round {
+            total,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.current >= self.total
+    }
+}
+impl Default for Round {
+    fn default() -> Self {
+        Round {
+            total: 10,
+            current: 0,
+        }
+    }
+}
+
+/// Modalities the player answered a match for in the current round, with
+/// the latency of their first answer.
+#[derive(Clone, Debug, Default)]
+pub struct Answer(HashMap<ModalityId, f32>);
+
+impl Answer {
+    /// Answer the modality `latency` seconds after the onset of the cues,
+    /// unless it was answered already.
+    pub fn set(&mut self, id: ModalityId, latency: f32) {
+        self.0.entry(id).or_insert(latency);
+    }
+
+    pub fn is_set(&self, id: ModalityId) -> bool {
+        self.0.contains_key(&id)
+    }
+
+    /// Seconds from the onset of the cues to the first answer.
+    pub fn latency(&self, id: ModalityId) -> Option<f32> {
+        self.0.get(&id).copied()
+    }
+
+    pub fn reset(&mut self) {
+        self.0.clear();
+    }
+}
@@ -0,0 +1,330 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{cue::CueKind, modality::ModalityId};
+
+/// How a response to a cue is scored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    /// A match answered, or a right arithmetic result.
+    Hit,
+    /// A match left unanswered, or a wrong arithmetic result.
+    Miss,
+    /// A match answered on a cue that was none.
+    FalseAlarm,
+    /// No match answered on a cue that was none.
+    CorrectRejection,
+}
+
+/// Signal detection measures of a score, telling how well matches are told
+/// apart from other cues regardless of how readily the player answers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Detection {
+    /// Share of the matches answered.
+    pub hit_rate: f32,
+    /// Share of the other cues answered as matches.
+    pub false_alarm_rate: f32,
+    /// Sensitivity d′, the distance between the z-scores of the hit and
+    /// false alarm rates.
+    pub d_prime: f32,
+    /// Response criterion c, positive for a conservative player who answers
+    /// few matches and negative for a liberal one who answers many.
+    pub criterion: f32,
+}
+
+/// Summary of the latencies of a kind of answer, in seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Latency {
+    pub count: usize,
+    pub mean: f32,
+    pub median: f32,
+}
+
+impl Latency {
+    /// Summary of `latencies`, `None` if there are none.
+    pub fn from_latencies(latencies: &[f32]) -> Option<Self> {
+        if latencies.is_empty() {
+            return None;
+        }
+
+        let mut sorted = latencies.to_vec();
+        sorted.sort_by(f32::total_cmp);
+        // the middle latency, or the mean of the two middle ones
+        let median = (sorted[(sorted.len() - 1) / 2] + sorted[sorted.len() / 2]) / 2.0;
+
+        Some(Latency {
+            count: sorted.len(),
+            mean: sorted.iter().sum::<f32>() / sorted.len() as f32,
+            median,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Score {
+    false_pos: usize,
+    true_pos: usize,
+    false_neg: usize,
+    true_neg: usize,
+    /// False positives on lures, also counted in `false_pos`.
+    lure_false_pos: usize,
+    /// Latencies of the answered matches, in seconds.
+    hit_latencies: Vec<f32>,
+    /// Latencies of the matches answered on other cues, in seconds.
+    false_alarm_latencies: Vec<f32>,
+    /// Score of every modality and combination channel on its own, in the
+    /// order they were first scored.
+    modalities: Vec<(ModalityId, Score)>,
+}
+
+impl Score {
+    /// Record whether a match was answered for a cue of the given kind.
+    pub fn record(&mut self, answered: bool, kind: CueKind) -> Outcome {
+        match (answered, kind) {
+            (true, CueKind::Match) => self.record_tp(),
+            (true, CueKind::Lure) => self.record_lure_fp(),
+            (true, CueKind::Other) => self.record_fp(),
+            (false, CueKind::Match) => self.record_fn(),
+            (false, _) => self.record_tn(),
+        }
+    }
+
+    /// Record whether the result typed in an arithmetic round was right.
+    pub fn record_result(&mut self, correct: bool) -> Outcome {
+        if correct {
+            self.record_tp()
+        } else {
+            self.record_fn()
+        }
+    }
+
+    /// Count an outcome already scored for a cue of the given kind, e.g. in
+    /// another score.
+    pub fn add(&mut self, outcome: Outcome, kind: CueKind) {
+        match outcome {
+            Outcome::Hit => self.true_pos += 1,
+            Outcome::Miss => self.false_neg += 1,
+            Outcome::FalseAlarm if kind == CueKind::Lure => {
+                self.false_pos += 1;
+                self.lure_false_pos += 1;
+            }
+            Outcome::FalseAlarm => self.false_pos += 1,
+            Outcome::CorrectRejection => self.true_neg += 1,
+        }
+    }
+
+    /// Record the latency of the answer given for an outcome, kept for hits
+    /// and false alarms only.
+    pub fn record_latency(&mut self, outcome: Outcome, latency: f32) {
+        match outcome {
+            Outcome::Hit => self.hit_latencies.push(latency),
+            Outcome::FalseAlarm => self.false_alarm_latencies.push(latency),
+            Outcome::Miss | Outcome::CorrectRejection => {}
+        }
+    }
+
+    /// Count an outcome already scored, and the latency of its answer, in
+    /// the score of its modality alone.
+    pub fn add_to_modality(
+        &mut self,
+        id: ModalityId,
+        outcome: Outcome,
+        kind: CueKind,
+        latency: Option<f32>,
+    ) {
+        let i = match self.modalities.iter().position(|(other, _)| *other == id) {
+            Some(i) => i,
+            None => {
+                self.modalities.push((id, Score::default()));
+                self.modalities.len() - 1
+            }
+        };
+        let score = &mut self.modalities[i].1;
+        score.add(outcome, kind);
+        if let Some(latency) = latency {
+            score.record_latency(outcome, latency);
+        }
+    }
+
+    /// Score of every modality and combination channel scored so far.
+    pub fn modalities(&self) -> impl Iterator<Item = (ModalityId, &Score)> {
+        self.modalities.iter().map(|(id, score)| (*id, score))
+    }
+
+    pub fn modality(&self, id: ModalityId) -> Option<&Score> {
+        self.modalities()
+            .find(|(other, _)| *other == id)
+            .map(|(_, score)| score)
+    }
+
+    pub fn record_fp(&mut self) -> Outcome {
+        self.false_pos += 1;
+        Outcome::FalseAlarm
+    }
+
+    pub fn record_lure_fp(&mut self) -> Outcome {
+        self.false_pos += 1;
+        self.lure_false_pos += 1;
+        Outcome::FalseAlarm
+    }
+
+    pub fn record_tp(&mut self) -> Outcome {
+        self.true_pos += 1;
+        Outcome::Hit
+    }
+
+    pub fn record_fn(&mut self) -> Outcome {
+        self.false_neg += 1;
+        Outcome::Miss
+    }
+
+    pub fn record_tn(&mut self) -> Outcome {
+        self.true_neg += 1;
+        Outcome::CorrectRejection
+    }
+
+    pub fn correct(&self) -> usize {
+        self.true_pos + self.true_neg
+    }
+
+    pub fn wrong(&self) -> usize {
+        self.false_pos + self.false_neg
+    }
+
+    pub fn lure_false_alarms(&self) -> usize {
+        self.lure_false_pos
+    }
+
+    pub fn accuracy(&self) -> f32 {
+        let total = self.correct() + self.wrong();
+        if total == 0 {
+            1.0
+        } else {
+            self.correct() as f32 / total as f32
+        }
+    }
+
+    pub fn accuracy_percent(&self) -> usize {
+        (self.accuracy() * 100.0) as usize
+    }
+
+    pub fn f1_score(&self) -> f32 {
+        if self.true_pos + self.false_neg == 0 {
+            1.0
+        } else {
+            self.true_pos as f32
+                / (self.true_pos as f32 + 0.5 * (self.false_pos as f32 + self.false_neg as f32))
+        }
+    }
+
+    pub fn f1_score_percent(&self) -> usize {
+        (self.f1_score() * 100.0) as usize
+    }
+
+    /// Latency of the answered matches, `None` without any.
+    pub fn hit_latency(&self) -> Option<Latency> {
+        Latency::from_latencies(&self.hit_latencies)
+    }
+
+    /// Latency of the false alarms, `None` without any.
+    pub fn false_alarm_latency(&self) -> Option<Latency> {
+        Latency::from_latencies(&self.false_alarm_latencies)
+    }
+
+    /// Share of the matches answered, `None` without any match.
+    pub fn hit_rate(&self) -> Option<f32> {
+        rate(self.true_pos, self.true_pos + self.false_neg)
+    }
+
+    /// Share of the other cues answered as matches, `None` without any.
+    pub fn false_alarm_rate(&self) -> Option<f32> {
+        rate(self.false_pos, self.false_pos + self.true_neg)
+    }
+
+    /// Signal detection measures, `None` unless there were both matches and
+    /// other cues.
+    ///
+    /// Rates of 0 and 1 have infinite z-scores, so they are moved half a
+    /// trial away from the bound before d′ and c are computed, after
+    /// Macmillan and Kaplan (1985).
+    pub fn detection(&self) -> Option<Detection> {
+        let hit_rate = self.hit_rate()?;
+        let false_alarm_rate = self.false_alarm_rate()?;
+
+        let hit = probit(corrected_rate(
+            self.true_pos,
+            self.true_pos + self.false_neg,
+        ));
+        let false_alarm = probit(corrected_rate(
+            self.false_pos,
+            self.false_pos + self.true_neg,
+        ));
+
+        Some(Detection {
+            hit_rate,
+            false_alarm_rate,
+            d_prime: (hit - false_alarm) as f32,
+            criterion: (-(hit + false_alarm) / 2.0) as f32,
+        })
+    }
+}
+
+fn rate(count: usize, total: usize) -> Option<f32> {
+    (total > 0).then(|| count as f32 / total as f32)
+}
+
+/// Rate of `count` out of `total`, kept half a trial away from 0 and 1.
+fn corrected_rate(count: usize, total: usize) -> f64 {
+    let half = 0.5 / total as f64;
+    (count as f64 / total as f64).clamp(half, 1.0 - half)
+}
+
+/// Inverse of the standard normal distribution function, after Acklam's
+/// rational approximation, to a relative error below 1.2e-9.
+fn probit(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    arithmetic::Operation,
+    cue::{CueEngine, CueKind, Symbol},
+    modality::ModalityId,
+    round::{Answer, Round},
+    score::{Outcome, Score},
+};
+
+/// What the player answers in every trial.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Task {
+    /// Which modalities match the cue N back.
+    #[default]
+    Match,
+    /// The result of the operation between the number shown and the one N
+    /// back.
+    Arithmetic(Operation),
+}
+
+/// Scored response to the cue of a modality.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CueResult {
+    pub id: ModalityId,
+    /// Cue shown, that of the modality compared for combination channels,
+    /// `None` only for a modality without any cue.
+    pub symbol: Option<Symbol>,
+    pub kind: CueKind,
+    /// Whether the player answered a match.
+    pub answered: bool,
+    /// `None` when there was nothing to answer, e.g. no number N back in an
+    /// arithmetic trial.
+    pub outcome: Option<Outcome>,
+    /// Seconds from the onset of the cues to the first answer of a match,
+    /// `None` if no match was answered.
+    pub latency: Option<f32>,
+}
+
+/// Scored responses to the cues of a trial.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrialResult {
+    pub round: usize,
+    pub cues: Vec<CueResult>,
+    /// Result typed in an arithmetic trial, `None` if the player gave none.
+    pub result: Option<i32>,
+    /// Blank interval after the cues of the trial, in seconds, if timed.
+    pub interval: Option<f32>,
+}
+
+/// A pause taken by the player, which does not count toward the trial.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pause {
+    /// Trial paused.
+    pub round: usize,
+    /// Duration of the pause, in seconds.
+    pub duration: f32,
+}
+
+/// A game of n-back, from the first trial to the last.
+///
+/// A session knows nothing about time: the caller collects the responses to
+/// the current trial and calls [`Session::step`] when the trial is over.
+/// The cues of the first trial are drawn with the session, so that every
+/// cue drawn is scored.
+pub struct Session {
+    engine: CueEngine,
+    task: Task,
+    round: Round,
+    score: Score,
+    answer: Answer,
+    /// Result answered in the current arithmetic trial.
+    result: Option<i32>,
+    pauses: Vec<Pause>,
+    /// Result of every trial over so far.
+    trials: Vec<TrialResult>,
+}
+
+impl Session {
+    pub fn new(engine: CueEngine, total: usize, task: Task) -> Self {
+        let mut session = Session {
+            engine,
+            task,
+            round: Round::with_total(total),
+            score: Score::default(),
+            answer: Answer::default(),
+            result: None,
+            pauses: Vec::new(),
+            trials: Vec::new(),
+        };
+        if !session.is_over() {
+            session.engine.new_cue();
+        }
+        session
+    }
+
+    pub fn engine(&self) -> &CueEngine {
+        &self.engine
+    }
+
+    pub fn task(&self) -> Task {
+        self.task
+    }
+
+    pub fn round(&self) -> &Round {
+        &self.round
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    /// Result of every trial over so far, in order.
+    pub fn trials(&self) -> &[TrialResult] {
+        &self.trials
+    }
+
+    /// Pauses taken so far, in order.
+    pub fn pauses(&self) -> &[Pause] {
+        &self.pauses
+    }
+
+    /// Record a pause of `duration` seconds in the current trial.
+    pub fn record_pause(&mut self, duration: f32) {
+        self.pauses.push(Pause {
+            round: self.round.current,
+            duration,
+        });
+    }
+
+    /// Answer a match of the modality in the current trial, `latency`
+    /// seconds after the onset of its cues. Only the first answer counts.
+    pub fn answer_match(&mut self, id: ModalityId, latency: f32) {
+        self.answer.set(id, latency);
+    }
+
+    /// Answer the result of the current arithmetic trial, `None` if the
+    /// player gave none.
+    pub fn answer_result(&mut self, result: Option<i32>) {
+        self.result = result;
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.round.is_last()
+    }
+
+    /// Score the responses to the current trial, whose blank interval lasted
+    /// `interval` seconds if timed, then draw the next one unless it was the
+    /// last.
+    pub fn step(&mut self, interval: Option<f32>) -> TrialResult {
+        let mut cues = Vec::new();
+        for (id, chain) in self.engine.chains() {
+            let kind = chain.kind();
+            let (outcome, latency) = match self.task {
+                Task::Match => (
+                    Some(self.score.record(self.answer.is_set(id), kind)),
+                    self.answer.latency(id),
+                ),
+                Task::Arithmetic(operation) => (
+                    operation
+                        .result(chain)
+                        .map(|result| self.score.record_result(self.result == Some(result))),
+                    None,
+                ),
+            };
+            cues.push(CueResult {
+                id,
+                symbol: chain.latest(),
+                kind,
+                answered: self.answer.is_set(id),
+                outcome,
+                latency,
+            });
+        }
+        if self.task == Task::Match {
+            for (id, channel) in self.engine.crosses() {
+                let kind = self.engine.cross_kind(channel);
+                let outcome = Some(self.score.record(self.answer.is_set(id), kind));
+                cues.push(CueResult {
+                    id,
+                    symbol: self.engine.chain(channel.cue).and_then(|c| c.latest()),
+                    kind,
+                    answered: self.answer.is_set(id),
+                    outcome,
+                    latency: self.answer.latency(id),
+                });
+            }
+        }
+
+        for cue in cues.iter() {
+            if let Some(outcome) = cue.outcome {
+                if let Some(latency) = cue.latency {
+                    self.score.record_latency(outcome, latency);
+                }
+                self.score
+                    .add_to_modality(cue.id, outcome, cue.kind, cue.latency);
+            }
+        }
+
+        let result = TrialResult {
+            round: self.round.current,
+            cues,
+            result: self.result,
+            interval,
+        };
+        self.trials.push(result.clone());
+
+        self.answer.reset();
+        self.result = None;
+        self.round.current += 1;
+        if !self.is_over() {
+            self.engine.new_cue();
+        }
+
+        result
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session::new(
+            CueEngine::default(),
+            Round::default().total,
+            Task::default(),
+        )
+    }
+}
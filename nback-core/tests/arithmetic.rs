@@ -0,0 +1,62 @@
+//! Checks of the results of arithmetic games: every operation must give a
+//! spread of answers rather than the same one over and over.
+
+use std::collections::HashMap;
+
+use nback_core::{
+    arithmetic::{Operation, NUMBERS},
+    cue::{CueEngine, CueSettings},
+    modality::ModalityId,
+};
+
+const TOTAL: usize = 24;
+const SEEDS: std::ops::Range<u64> = 0..50;
+
+const NUMBER: ModalityId = ModalityId("number");
+
+/// Counts of every result of `operation` over seeded games of `TOTAL` trials.
+fn results(operation: Operation) -> HashMap<i32, usize> {
+    let mut counts = HashMap::new();
+    for seed in SEEDS {
+        let mut engine = CueEngine::with_seed(seed)
+            .with_chain(NUMBER, NUMBERS, &CueSettings::default())
+            .with_rule(NUMBER, operation.rule());
+        for _ in 0..TOTAL {
+            engine.new_cue();
+            if let Some(result) = operation.result(engine.chain(NUMBER).unwrap()) {
+                *counts.entry(result).or_default() += 1;
+            }
+        }
+    }
+    counts
+}
+
+#[test]
+fn results_are_spread() {
+    for operation in Operation::ALL {
+        let counts = results(operation);
+        let total: usize = counts.values().sum();
+        let most = counts.values().copied().max().unwrap();
+        assert!(
+            most * 2 < total,
+            "{}: one result answers {} of {} trials",
+            operation.label(),
+            most,
+            total
+        );
+        assert!(
+            counts.len() >= 5,
+            "{}: only {} distinct results",
+            operation.label(),
+            counts.len()
+        );
+    }
+}
+
+#[test]
+fn divisions_are_not_mostly_one() {
+    let counts = results(Operation::Divide);
+    let total: usize = counts.values().sum();
+    let ones = counts.get(&1).copied().unwrap_or_default();
+    assert!(ones * 2 < total, "{} of {} results are 1", ones, total);
+}
@@ -0,0 +1,74 @@
+//! Checks of the signal detection measures of a score against values worked
+//! out by hand, perfect and null rates included.
+
+use nback_core::{cue::CueKind, score::Score};
+
+const TOLERANCE: f32 = 1e-4;
+
+/// Score of `hits` of `matches` answered, and `false_alarms` of `others`.
+fn score(hits: usize, matches: usize, false_alarms: usize, others: usize) -> Score {
+    let mut score = Score::default();
+    for i in 0..matches {
+        score.record(i < hits, CueKind::Match);
+    }
+    for i in 0..others {
+        score.record(i < false_alarms, CueKind::Other);
+    }
+    score
+}
+
+fn assert_detection(score: &Score, d_prime: f32, criterion: f32) {
+    let detection = score.detection().unwrap();
+    assert!(
+        (detection.d_prime - d_prime).abs() < TOLERANCE,
+        "d′ is {}, not {}",
+        detection.d_prime,
+        d_prime
+    );
+    assert!(
+        (detection.criterion - criterion).abs() < TOLERANCE,
+        "c is {}, not {}",
+        detection.criterion,
+        criterion
+    );
+}
+
+#[test]
+fn detection_of_unbiased_player() {
+    // z(0.8) = 0.841621
+    assert_detection(&score(8, 10, 2, 10), 1.683242, 0.0);
+}
+
+#[test]
+fn detection_of_liberal_player() {
+    // z(0.9) = 1.281552, z(0.3) = -0.524401
+    assert_detection(&score(9, 10, 3, 10), 1.805952, -0.378575);
+}
+
+#[test]
+fn perfect_rates_are_corrected() {
+    // 10 of 10 and 0 of 10 count as 0.95 and 0.05, z(0.95) = 1.644854
+    let perfect = score(10, 10, 0, 10);
+    assert_detection(&perfect, 3.289707, 0.0);
+    // the rates themselves are not corrected
+    let detection = perfect.detection().unwrap();
+    assert_eq!(detection.hit_rate, 1.0);
+    assert_eq!(detection.false_alarm_rate, 0.0);
+
+    // answering every cue tells nothing apart
+    assert_detection(&score(10, 10, 10, 10), 0.0, -1.644854);
+}
+
+#[test]
+fn null_rates_are_corrected() {
+    // 0 of 20 and 20 of 20 count as 0.025 and 0.975, z(0.025) = -1.959964
+    assert_detection(&score(0, 20, 20, 20), -3.919928, 0.0);
+    // answering nothing
+    assert_detection(&score(0, 10, 0, 10), 0.0, 1.644854);
+}
+
+#[test]
+fn no_detection_without_matches_or_other_cues() {
+    assert_eq!(score(0, 0, 2, 10).detection(), None);
+    assert_eq!(score(5, 10, 0, 0).detection(), None);
+}
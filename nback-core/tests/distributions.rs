@@ -0,0 +1,220 @@
+//! Statistical checks of the cues drawn by the engine: every symbol of the
+//! alphabet must come up as often as the others, and no other symbol at all.
+
+use nback_core::{
+    alphabet::Alphabet,
+    cue::{CueEngine, CueSettings, Symbol},
+    modality::ModalityId,
+};
+
+const SAMPLES: usize = 20_000;
+const SEEDS: [u64; 3] = [1, 42, 0xdead_beef];
+
+const POSITION: ModalityId = ModalityId("position");
+const SOUND: ModalityId = ModalityId("sound");
+
+/// Upper bound of the chi-squared statistic with `dof` degrees of freedom
+/// exceeded with a probability of 1e-4, after Wilson and Hilferty.
+fn chi_squared_bound(dof: usize) -> f64 {
+    const Z: f64 = 3.719;
+    let k = dof as f64;
+    let a = 2.0 / (9.0 * k);
+    k * (1.0 - a + Z * a.sqrt()).powi(3)
+}
+
+/// Counts of every symbol among `symbols` drawn by the chain of `id`.
+fn counts(engine: &mut CueEngine, id: ModalityId, symbols: usize) -> Vec<usize> {
+    let mut counts = vec![0; symbols];
+    for _ in 0..SAMPLES {
+        engine.new_cue();
+        counts[engine.chain(id).unwrap().latest().unwrap()] += 1;
+    }
+    counts
+}
+
+/// Check that the symbols of `alphabet` are drawn uniformly and no others.
+fn assert_uniform(counts: &[usize], alphabet: &[Symbol]) {
+    for (symbol, count) in counts.iter().enumerate() {
+        if !alphabet.contains(&symbol) {
+            assert_eq!(*count, 0, "symbol {} is outside the alphabet", symbol);
+        }
+    }
+
+    let total: usize = alphabet.iter().map(|symbol| counts[*symbol]).sum();
+    let expected = total as f64 / alphabet.len() as f64;
+    let statistic: f64 = alphabet
+        .iter()
+        .map(|symbol| (counts[*symbol] as f64 - expected).powi(2) / expected)
+        .sum();
+    let bound = chi_squared_bound(alphabet.len() - 1);
+    assert!(
+        statistic < bound,
+        "counts {:?} are not uniform: chi-squared {:.1} >= {:.1}",
+        counts,
+        statistic,
+        bound
+    );
+}
+
+#[test]
+fn full_sets_are_uniform() {
+    for symbols in [5, 8, 9, 16, 25] {
+        for seed in SEEDS {
+            let mut engine =
+                CueEngine::with_seed(seed).with_chain(SOUND, symbols, &CueSettings::default());
+            let all: Vec<Symbol> = (0..symbols).collect();
+            assert_uniform(&counts(&mut engine, SOUND, symbols), &all);
+        }
+    }
+}
+
+#[test]
+fn alphabets_are_uniform() {
+    let alphabets = [
+        (5, Alphabet::first(4)),
+        (8, Alphabet::first(2)),
+        (8, Alphabet::from_symbols([0, 2, 3, 5, 7])),
+        (9, Alphabet::from_symbols([8])),
+    ];
+    for (symbols, alphabet) in alphabets {
+        for seed in SEEDS {
+            let settings = CueSettings {
+                alphabet: Some(alphabet),
+                ..CueSettings::default()
+            };
+            let mut engine = CueEngine::with_seed(seed).with_chain(SOUND, symbols, &settings);
+            let active = alphabet.symbols(symbols);
+            assert_eq!(engine.chain(SOUND).unwrap().alphabet(), active);
+
+            let counts = counts(&mut engine, SOUND, symbols);
+            if active.len() > 1 {
+                assert_uniform(&counts, &active);
+            } else {
+                assert_eq!(counts[active[0]], SAMPLES);
+            }
+        }
+    }
+}
+
+#[test]
+fn alphabets_outside_the_modality_draw_every_symbol() {
+    let settings = CueSettings {
+        alphabet: Some(Alphabet::from_symbols([10, 11])),
+        ..CueSettings::default()
+    };
+    assert_eq!(settings.active_symbols(8), (0..8).collect::<Vec<_>>());
+
+    let mut engine = CueEngine::with_seed(7).with_chain(SOUND, 8, &settings);
+    let all: Vec<Symbol> = (0..8).collect();
+    assert_uniform(&counts(&mut engine, SOUND, 8), &all);
+}
+
+#[test]
+fn lures_keep_alphabets_uniform() {
+    let alphabet = Alphabet::first(6);
+    for seed in SEEDS {
+        let settings = CueSettings {
+            n: 3,
+            lure_rate: CueSettings::LURE_RATE,
+            alphabet: Some(alphabet),
+            ..CueSettings::default()
+        };
+        let mut engine = CueEngine::with_seed(seed).with_chain(SOUND, 8, &settings);
+        assert_uniform(&counts(&mut engine, SOUND, 8), &alphabet.symbols(8));
+    }
+}
+
+#[test]
+fn match_rate_follows_the_alphabet_size() {
+    for size in [2, 4, 8] {
+        let settings = CueSettings {
+            alphabet: Some(Alphabet::first(size)),
+            ..CueSettings::default()
+        };
+        let mut engine = CueEngine::with_seed(3).with_chain(SOUND, 8, &settings);
+        let mut matches = 0;
+        for _ in 0..SAMPLES {
+            engine.new_cue();
+            matches += engine.chain(SOUND).unwrap().is_match() as usize;
+        }
+
+        // a quarter of the cues are drawn as matches, and the others match
+        // by chance; the first N cues cannot match
+        let drawn = (SAMPLES - settings.n) as f64;
+        let p = 0.25 + 0.75 / size as f64;
+        let deviation = (drawn * p * (1.0 - p)).sqrt();
+        let z = (matches as f64 - drawn * p).abs() / deviation;
+        assert!(
+            z < 4.0,
+            "{} matches out of {} for {} symbols",
+            matches,
+            drawn,
+            size
+        );
+    }
+}
+
+#[test]
+fn distinct_tiles_are_uniform() {
+    let ids = [
+        POSITION,
+        ModalityId("position 2"),
+        ModalityId("position 3"),
+        ModalityId("position 4"),
+    ];
+    let settings = CueSettings {
+        alphabet: Some(Alphabet::first(7)),
+        ..CueSettings::default()
+    };
+    let mut engine = ids
+        .iter()
+        .fold(CueEngine::with_seed(11), |engine, id| {
+            engine.with_chain(*id, 9, &settings)
+        })
+        .with_distinct(ids);
+
+    let mut counts = vec![vec![0; 9]; ids.len()];
+    for _ in 0..SAMPLES {
+        engine.new_cue();
+        let mut cues: Vec<Symbol> = ids
+            .iter()
+            .map(|id| engine.chain(*id).unwrap().latest().unwrap())
+            .collect();
+        for (counts, cue) in counts.iter_mut().zip(&cues) {
+            counts[*cue] += 1;
+        }
+        cues.sort_unstable();
+        cues.dedup();
+        assert_eq!(cues.len(), ids.len(), "tiles share a cell");
+    }
+
+    for counts in counts.iter() {
+        assert_uniform(counts, &Alphabet::first(7).symbols(9));
+    }
+}
+
+#[test]
+fn distinct_tiles_overflow_small_alphabets() {
+    let ids = [POSITION, ModalityId("position 2"), ModalityId("position 3")];
+    let settings = CueSettings {
+        alphabet: Some(Alphabet::first(2)),
+        ..CueSettings::default()
+    };
+    let mut engine = ids
+        .iter()
+        .fold(CueEngine::with_seed(5), |engine, id| {
+            engine.with_chain(*id, 9, &settings)
+        })
+        .with_distinct(ids);
+
+    for _ in 0..1000 {
+        engine.new_cue();
+        let mut cues: Vec<Symbol> = ids
+            .iter()
+            .map(|id| engine.chain(*id).unwrap().latest().unwrap())
+            .collect();
+        cues.sort_unstable();
+        cues.dedup();
+        assert_eq!(cues.len(), ids.len(), "tiles share a cell");
+    }
+}
@@ -0,0 +1,103 @@
+//! Checks of saved plans: a plan reads back as written and replays the game
+//! it was recorded from, combination channels and lures included.
+
+use nback_core::{
+    cue::{CrossChannel, CueEngine, CueSettings},
+    modality::ModalityId,
+    plan::{PlanError, SessionPlan},
+    session::Task,
+};
+
+const TOTAL: usize = 22;
+
+const POSITION: ModalityId = ModalityId("position");
+const SOUND: ModalityId = ModalityId("sound");
+const LETTER: ModalityId = ModalityId("letter");
+const SOUND_LETTER: ModalityId = ModalityId("sound-letter");
+const LETTER_SOUND: ModalityId = ModalityId("letter-sound");
+
+/// Modalities of the game, with their number of distinct cues.
+const REGISTERED: [(ModalityId, usize); 5] = [
+    (POSITION, 9),
+    (SOUND, 8),
+    (LETTER, 8),
+    (SOUND_LETTER, 0),
+    (LETTER_SOUND, 0),
+];
+
+fn combination(seed: u64) -> CueEngine {
+    let settings = CueSettings {
+        lure_rate: CueSettings::LURE_RATE,
+        ..CueSettings::default()
+    };
+    CueEngine::with_seed(seed)
+        .with_chain(POSITION, 9, &CueSettings::default())
+        .with_chain(SOUND, 8, &settings)
+        .with_chain(LETTER, 8, &settings)
+        .with_cross(
+            SOUND_LETTER,
+            CrossChannel {
+                cue: SOUND,
+                target: LETTER,
+            },
+        )
+        .with_cross(
+            LETTER_SOUND,
+            CrossChannel {
+                cue: LETTER,
+                target: SOUND,
+            },
+        )
+}
+
+#[test]
+fn combination_plan_reloads_as_saved() {
+    let plan = SessionPlan::record(combination(42), TOTAL, 3.0, Task::Match);
+
+    let path = std::env::temp_dir().join(format!("nback-plan-{}.ron", std::process::id()));
+    plan.save(&path).unwrap();
+    let loaded = SessionPlan::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    let loaded = loaded.unwrap();
+    assert_eq!(loaded, plan);
+
+    assert_eq!(loaded.crosses.len(), 2);
+    assert_eq!(loaded.modalities[1].lure_rate, CueSettings::LURE_RATE);
+
+    let mut recorded = combination(42);
+    let mut replayed = loaded.engine(&REGISTERED).unwrap();
+    assert_eq!(
+        replayed.crosses().collect::<Vec<_>>(),
+        recorded.crosses().collect::<Vec<_>>()
+    );
+    for _ in 0..TOTAL {
+        recorded.new_cue();
+        replayed.new_cue();
+        for (id, channel) in recorded.crosses() {
+            assert_eq!(
+                replayed.cross_kind(channel),
+                recorded.cross_kind(channel),
+                "{} differs",
+                id.0
+            );
+        }
+    }
+}
+
+#[test]
+fn plan_comparing_a_modality_not_played_is_invalid() {
+    let mut plan = SessionPlan::record(combination(1), TOTAL, 3.0, Task::Match);
+    plan.crosses[0].target = "color".to_string();
+    assert!(plan.engine(&REGISTERED).is_err());
+}
+
+#[test]
+fn plan_with_other_symbols_than_the_game_is_invalid() {
+    let mut plan = SessionPlan::record(combination(1), TOTAL, 3.0, Task::Match);
+    // nine sounds, of which the game only has eight
+    plan.modalities[1].symbols = 9;
+    assert!(matches!(
+        plan.engine(&REGISTERED),
+        Err(PlanError::Invalid(reason)) if reason.contains("symbols")
+    ));
+}
@@ -0,0 +1,118 @@
+//! Checks of the progression rules: Brain Workshop's N goes down after three
+//! bad games in a row only, and Jaeggi's follows the errors of the worst
+//! modality across its limits.
+
+use nback_core::{
+    cue::CueKind,
+    modality::ModalityId,
+    progression::{BrainWorkshop, Direction, Jaeggi, LevelChange, ProgressionRule, Reason},
+    score::Score,
+    session::Task,
+};
+
+const POSITION: ModalityId = ModalityId("position");
+const SOUND: ModalityId = ModalityId("sound");
+
+/// Score of a game of 20 trials with `hits` of its 10 matches answered and
+/// no false alarm.
+fn game(hits: usize) -> Score {
+    let mut score = Score::default();
+    for i in 0..10 {
+        score.record(i < hits, CueKind::Match);
+        score.record(false, CueKind::Other);
+    }
+    score
+}
+
+/// Score of a game where every modality made the given number of errors,
+/// missed matches among 10.
+fn errors(modalities: &[(ModalityId, usize)]) -> Score {
+    let mut score = Score::default();
+    for (id, errors) in modalities {
+        for i in 0..10 {
+            let outcome = score.record(i >= *errors, CueKind::Match);
+            score.add_to_modality(*id, outcome, CueKind::Match, None);
+        }
+    }
+    score
+}
+
+#[test]
+fn three_games_under_half_go_down_one_level() {
+    let mut rule = BrainWorkshop::default();
+    // 2 of 10 matches: an F1 score of 33%
+    let bad = game(2);
+    assert!(bad.f1_score_percent() < BrainWorkshop::DOWN_PERCENT);
+
+    assert_eq!(rule.decide(&bad, Task::Match), None);
+    assert_eq!(rule.decide(&bad, Task::Match), None);
+    assert_eq!(rule.strikes(), 2);
+    assert_eq!(
+        rule.decide(&bad, Task::Match),
+        Some(LevelChange {
+            direction: Direction::Down,
+            reason: Reason::Strikes {
+                strikes: BrainWorkshop::STRIKES,
+                threshold: BrainWorkshop::DOWN_PERCENT,
+            },
+        })
+    );
+    assert_eq!(rule.strikes(), 0);
+}
+
+#[test]
+fn game_from_80_percent_resets_the_strikes() {
+    let mut rule = BrainWorkshop::default();
+    let bad = game(2);
+    // 8 of 10 matches: an F1 score of 88%
+    let good = game(8);
+    assert!(good.f1_score_percent() >= BrainWorkshop::UP_PERCENT);
+
+    rule.decide(&bad, Task::Match);
+    rule.decide(&bad, Task::Match);
+    let change = rule.decide(&good, Task::Match).unwrap();
+    assert_eq!(change.direction, Direction::Up);
+    assert_eq!(rule.strikes(), 0);
+
+    // two more bad games are not three in a row
+    assert_eq!(rule.decide(&bad, Task::Match), None);
+    assert_eq!(rule.decide(&bad, Task::Match), None);
+    assert_eq!(rule.strikes(), 2);
+}
+
+#[test]
+fn jaeggi_goes_down_over_five_errors() {
+    let score = errors(&[(POSITION, 1), (SOUND, 6)]);
+    assert_eq!(
+        Jaeggi.decide(&score, Task::Match),
+        Some(LevelChange {
+            direction: Direction::Down,
+            reason: Reason::ManyErrors {
+                id: SOUND,
+                errors: 6,
+                limit: Jaeggi::DOWN_ERRORS,
+            },
+        })
+    );
+
+    let score = errors(&[(POSITION, 1), (SOUND, 5)]);
+    assert_eq!(Jaeggi.decide(&score, Task::Match), None);
+}
+
+#[test]
+fn jaeggi_goes_up_under_three_errors() {
+    let score = errors(&[(POSITION, 2), (SOUND, 1)]);
+    assert_eq!(
+        Jaeggi.decide(&score, Task::Match),
+        Some(LevelChange {
+            direction: Direction::Up,
+            reason: Reason::FewErrors {
+                most: 2,
+                limit: Jaeggi::UP_ERRORS,
+            },
+        })
+    );
+
+    let score = errors(&[(POSITION, 2), (SOUND, 3)]);
+    assert_eq!(Jaeggi.decide(&score, Task::Match), None);
+}
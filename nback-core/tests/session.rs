@@ -0,0 +1,133 @@
+//! Checks of the trials scored by a session: every cue drawn is scored, no
+//! trial is scored before its cues are drawn, the matches scored are the
+//! ones planned, and the log of the trials reads back as written.
+
+use nback_core::{
+    block::MatchQuota,
+    cue::{CrossChannel, CueEngine, CueKind, CueSettings},
+    log::SessionLog,
+    modality::ModalityId,
+    session::{Session, Task},
+};
+
+const TOTAL: usize = 22;
+const SEEDS: [u64; 3] = [1, 42, 0xdead_beef];
+
+const POSITION: ModalityId = ModalityId("position");
+const SOUND: ModalityId = ModalityId("sound");
+const POSITION_SOUND: ModalityId = ModalityId("position-sound");
+
+fn engines(seed: u64) -> Vec<CueEngine> {
+    let settings = CueSettings::default();
+    vec![
+        CueEngine::with_seed(seed).with_chain(POSITION, 8, &settings),
+        CueEngine::with_seed(seed)
+            .with_chain(POSITION, 8, &settings)
+            .with_chain(SOUND, 8, &settings)
+            .with_cross(
+                POSITION_SOUND,
+                CrossChannel {
+                    cue: POSITION,
+                    target: SOUND,
+                },
+            ),
+        CueEngine::with_seed(seed)
+            .with_chain(POSITION, 8, &settings)
+            .with_chain(SOUND, 8, &settings)
+            .with_variable_n(),
+    ]
+}
+
+/// Play a session to the end without answering anything.
+fn play(engine: CueEngine) -> Session {
+    let mut session = Session::new(engine, TOTAL, Task::Match);
+    while !session.is_over() {
+        session.step(None);
+    }
+    session
+}
+
+#[test]
+fn every_trial_is_scored_once() {
+    for seed in SEEDS {
+        for engine in engines(seed) {
+            let session = play(engine);
+            let rounds: Vec<usize> = session.trials().iter().map(|t| t.round).collect();
+            assert_eq!(rounds, (0..TOTAL).collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+fn no_trial_is_scored_without_its_cues() {
+    for seed in SEEDS {
+        for engine in engines(seed) {
+            let session = play(engine);
+            for trial in session.trials() {
+                for cue in trial.cues.iter() {
+                    assert!(
+                        cue.symbol.is_some(),
+                        "seed {}: {} has no cue in trial {}",
+                        seed,
+                        cue.id.0,
+                        trial.round
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn scored_matches_fill_the_quota() {
+    let quota = MatchQuota::JAEGGI;
+    let settings = CueSettings::default();
+    for seed in 0..200 {
+        let engine = CueEngine::with_seed(seed)
+            .with_chain(POSITION, 8, &settings)
+            .with_chain(SOUND, 8, &settings)
+            .with_quota(quota, TOTAL);
+        let session = play(engine);
+        for id in [POSITION, SOUND] {
+            let matches = session
+                .trials()
+                .iter()
+                .flat_map(|trial| trial.cues.iter())
+                .filter(|cue| cue.id == id && cue.kind == CueKind::Match)
+                .count();
+            assert_eq!(
+                matches,
+                quota.single + quota.shared,
+                "seed {}: {} matches of {}",
+                seed,
+                matches,
+                id.0
+            );
+        }
+    }
+}
+
+#[test]
+fn log_reads_back_as_written() {
+    for seed in SEEDS {
+        for engine in engines(seed) {
+            let mut session = Session::new(engine, TOTAL, Task::Match);
+            while !session.is_over() {
+                // answer a match of the position every other trial
+                if session.round().current.is_multiple_of(2) {
+                    session.answer_match(POSITION, 0.25 * session.round().current as f32);
+                }
+                if session.round().current == 3 {
+                    session.record_pause(1.5);
+                }
+                session.step(Some(2.5));
+            }
+
+            let log = SessionLog::of(&session);
+            assert!(log.trials[0].cues.iter().all(|cue| cue.symbol.is_some()));
+
+            let text = log.to_ron().unwrap();
+            assert_eq!(SessionLog::from_ron(&text).unwrap(), log);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Checks of the cues a new one is compared with in crab-back games: 1, 3,
+//! 5... back up to 2N-1, then over again.
+
+use nback_core::{
+    cue::{CueEngine, CueKind, CueSettings, TargetOffset},
+    modality::ModalityId,
+};
+
+const TOTAL: usize = 12;
+const SEEDS: [u64; 3] = [1, 42, 0xdead_beef];
+
+const POSITION: ModalityId = ModalityId("position");
+
+#[test]
+fn crab_offsets_cycle_over_odd_distances() {
+    let offsets: Vec<usize> = (0..7).map(|t| TargetOffset::Crab.offset(3, t)).collect();
+    assert_eq!(offsets, [1, 3, 5, 1, 3, 5, 1]);
+    assert_eq!(TargetOffset::Crab.max_offset(3), 5);
+
+    let offsets: Vec<usize> = (0..5).map(|t| TargetOffset::Crab.offset(2, t)).collect();
+    assert_eq!(offsets, [1, 3, 1, 3, 1]);
+}
+
+#[test]
+fn crab_chain_compares_its_cues_at_the_offsets() {
+    let settings = CueSettings {
+        n: 3,
+        ..CueSettings::default()
+    };
+    for seed in SEEDS {
+        let mut engine = CueEngine::with_seed(seed)
+            .with_chain(POSITION, 8, &settings)
+            .with_target(TargetOffset::Crab);
+        for trial in 0..TOTAL {
+            engine.new_cue();
+            let chain = engine.chain(POSITION).unwrap();
+            let offset = 1 + 2 * (trial % 3);
+            assert_eq!(chain.offset(), offset, "seed {}: trial {}", seed, trial);
+
+            let matches = chain
+                .recall(offset)
+                .is_some_and(|cue| chain.latest() == Some(cue));
+            assert_eq!(
+                chain.kind() == CueKind::Match,
+                matches,
+                "seed {}: trial {}",
+                seed,
+                trial
+            );
+        }
+    }
+}
@@ -5,6 +5,7 @@ use crate::palette;
 // tile
 pub const TILE_SIZE: f32 = 100.0;
 pub const TILE_SPACING: f32 = 10.0;
+pub const TILE_SHAPE_RADIUS: f32 = 30.0;
 pub const TILE_SOUND_C: &str = "sounds/letters/c.ogg";
 pub const TILE_SOUND_H: &str = "sounds/letters/h.ogg";
 pub const TILE_SOUND_K: &str = "sounds/letters/k.ogg";
@@ -13,6 +14,12 @@ pub const TILE_SOUND_Q: &str = "sounds/letters/q.ogg";
 pub const TILE_SOUND_R: &str = "sounds/letters/r.ogg";
 pub const TILE_SOUND_S: &str = "sounds/letters/s.ogg";
 pub const TILE_SOUND_T: &str = "sounds/letters/t.ogg";
+/// Middle C, the root of the scales of the pitch modality, in Hz.
+pub const TONE_ROOT: f32 = 261.63;
+pub const TONE_SAMPLE_RATE: u32 = 44_100;
+pub const TONE_DURATION: f32 = 0.5;
+pub const TONE_FADE: f32 = 0.02;
+pub const TONE_VOLUME: f32 = 0.5;
 
 // splash screen
 pub const SPLASH_SCREEN_DURATION: f32 = 1.0;
@@ -20,3 +27,8 @@ pub const SPLASH_SCREEN_DURATION: f32 = 1.0;
 // game/menu screen
 pub const WALL_COLOR: Color = palette::WHITE;
 pub const WALL_THICKNESS: f32 = 4.0;
+
+// session plan, saved and loaded from the working directory
+pub const PLAN_FILE: &str = "plan.ron";
+// log of the latest game, saved next to the plan
+pub const LOG_FILE: &str = "log.ron";
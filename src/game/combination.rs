@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use nback_core::cue::CrossChannel;
+
+use super::{
+    modality::{CrossChannelPlugin, Modality, ModalityId},
+    tile::{letter::LetterModality, sound::SoundModality},
+};
+
+/// Letter heard matching the letter seen N back.
+pub const SEEN_HEARD: ModalityId = ModalityId("seen-heard");
+/// Letter seen matching the letter heard N back.
+pub const HEARD_SEEN: ModalityId = ModalityId("heard-seen");
+
+/// Register the channels of combination games, which compare the letter seen
+/// on the tile and the letter heard with each other on top of themselves.
+pub struct CombinationPlugin;
+
+impl Plugin for CombinationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            CrossChannelPlugin {
+                id: SEEN_HEARD,
+                label: "Seen-heard",
+                abbrev: "S-H",
+                key: KeyCode::KeyB,
+                channel: CrossChannel {
+                    cue: SoundModality::ID,
+                    target: LetterModality::ID,
+                },
+            },
+            CrossChannelPlugin {
+                id: HEARD_SEEN,
+                label: "Heard-seen",
+                abbrev: "H-S",
+                key: KeyCode::KeyN,
+                channel: CrossChannel {
+                    cue: LetterModality::ID,
+                    target: SoundModality::ID,
+                },
+            },
+        ));
+    }
+}
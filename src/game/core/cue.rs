@@ -1,12 +1,6 @@
-use std::collections::VecDeque;
-
 use bevy::prelude::*;
-use rand::{
-    distributions::{Distribution, Standard},
-    Rng,
-};
-
-use crate::game::tile::{color::TileColor, position::TilePosition, sound::TileSound};
+use nback_core::{jitter::Jitter, modality::ModalityId};
+use rand::{rngs::StdRng, SeedableRng};
 
 #[derive(Component, Deref, DerefMut)]
 pub struct CueTimer(pub Timer);
@@ -23,139 +17,64 @@ impl Default for CueTimer {
     }
 }
 
-/// Memorization and generation of new cues.
-pub struct CueChain<T> {
-    short_memory: VecDeque<T>,
-}
-
-impl<T: Default> Default for CueChain<T> {
-    fn default() -> Self {
-        CueChain::with_n_back(2)
-    }
+/// Durations of the trials of a game: the cues are shown for a set time,
+/// then the tile stays hidden for an interval drawn for every trial.
+#[derive(Component)]
+pub struct TrialTiming {
+    pub stimulus: f32,
+    pub interval: f32,
+    pub jitter: Jitter,
+    /// Interval drawn for the current trial.
+    current: f32,
+    /// Interval of every trial over so far.
+    intervals: Vec<f32>,
+    rng: StdRng,
 }
 
-impl<T: Default> CueChain<T> {
-    pub fn with_n_back(n: usize) -> Self {
-        let mut cc = CueChain {
-            short_memory: VecDeque::new(),
-        };
-
-        for _ in 0..n + 1 {
-            cc.short_memory.push_front(default());
+impl TrialTiming {
+    /// Timing drawing its intervals from `seed`, the first one included.
+    /// The seed is salted the way the cue chains salt theirs, so that the
+    /// intervals do not follow the draws of the cues.
+    pub fn new(stimulus: f32, interval: f32, jitter: Jitter, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed ^ ModalityId("timing").stable_hash());
+        TrialTiming {
+            stimulus,
+            interval,
+            jitter,
+            current: jitter.sample(interval, &mut rng),
+            intervals: Vec::new(),
+            rng,
         }
-
-        cc
     }
 
-    pub fn n_back(&self) -> usize {
-        self.short_memory.len() - 1
+    /// Interval of the current trial, in seconds.
+    pub fn current(&self) -> f32 {
+        self.current
     }
-}
-
-impl<T> CueChain<T>
-where
-    Standard: Distribution<T>,
-    T: Clone + PartialEq + Default,
-{
-    pub fn gen(&mut self) -> T {
-        let mut rng = rand::thread_rng();
-        let y = rng.gen::<f64>();
-
-        let cue = if y < 0.25 && *self.short_memory.front().unwrap() != default() {
-            self.short_memory.front().unwrap().clone()
-        } else {
-            rand::random()
-        };
-
-        self.short_memory.push_back(cue);
-        self.short_memory.pop_front();
 
-        (*self.short_memory.back().unwrap()).clone()
+    /// Duration of the current trial, its cues and its interval, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.stimulus + self.current
     }
-}
 
-impl<T: PartialEq + Default> CueChain<T> {
-    pub fn is_match(&self) -> bool {
-        if self.short_memory.front() != Some(&default()) {
-            self.short_memory.back() == self.short_memory.front()
-        } else {
-            false
-        }
+    pub fn intervals(&self) -> &[f32] {
+        &self.intervals
     }
-}
 
-#[derive(Component, Resource)]
-pub struct CueEngine {
-    n: usize,
-    pub positions: Option<CueChain<TilePosition>>,
-    pub colors: Option<CueChain<TileColor>>,
-    pub sounds: Option<CueChain<TileSound>>,
-}
-
-impl CueEngine {
-    pub fn with(n: usize, position: bool, color: bool, sound: bool) -> Self {
-        let positions = if position {
-            Some(CueChain::with_n_back(n))
-        } else {
-            None
-        };
-
-        let colors = if color {
-            Some(CueChain::with_n_back(n))
-        } else {
-            None
-        };
-
-        let sounds = if sound {
-            Some(CueChain::with_n_back(n))
-        } else {
-            None
-        };
-
-        CueEngine {
-            n,
-            positions,
-            colors,
-            sounds,
+    /// Mean duration of the trials over so far, the set one before any.
+    pub fn mean_duration(&self) -> f32 {
+        if self.intervals.is_empty() {
+            return self.stimulus + self.interval;
         }
+        self.stimulus + self.intervals.iter().sum::<f32>() / self.intervals.len() as f32
     }
 
-    pub fn n(&self) -> usize {
-        self.n
-    }
-
-    pub fn new_cue(&mut self) -> (Option<TilePosition>, Option<TileColor>, Option<TileSound>) {
-        let new_position = if self.positions.is_some() {
-            Some(self.positions.as_mut().unwrap().gen())
-        } else {
-            None
-        };
-
-        let new_color = if self.colors.is_some() {
-            Some(self.colors.as_mut().unwrap().gen())
-        } else {
-            None
-        };
-
-        let new_sound = if self.sounds.is_some() {
-            Some(self.sounds.as_mut().unwrap().gen())
-        } else {
-            None
-        };
-
-        (new_position, new_color, new_sound)
-    }
-}
-
-impl Default for CueEngine {
-    fn default() -> Self {
-        let n = 2;
-
-        CueEngine {
-            n,
-            positions: Some(CueChain::with_n_back(n)),
-            colors: Some(CueChain::with_n_back(n)),
-            sounds: Some(CueChain::with_n_back(n)),
-        }
+    /// End the current trial, returning its interval, and draw the duration
+    /// of the next one.
+    pub fn next_trial(&mut self) -> (f32, f32) {
+        let interval = self.current;
+        self.intervals.push(interval);
+        self.current = self.jitter.sample(self.interval, &mut self.rng);
+        (interval, self.duration())
     }
 }
@@ -1,22 +1,18 @@
-use self::{
-    cue::{CueEngine, CueTimer},
-    round::Round,
-    score::Score,
-    state::GameState,
-};
+use self::{cue::CueTimer, state::GameState};
 
 use bevy::prelude::*;
+use nback_core::session::Session;
 
 pub mod cue;
-pub mod round;
-pub mod score;
 pub mod state;
 
+/// The session of the game being played.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct GameSession(pub Session);
+
 #[derive(Bundle, Default)]
 pub struct DualNBackBundle {
-    pub engine: CueEngine,
+    pub session: GameSession,
     pub state: GameState,
     pub timer: CueTimer,
-    pub round: Round,
-    pub score: Score,
 }
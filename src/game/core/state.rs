@@ -1,8 +1,17 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
-#[derive(Component, Default, Eq, PartialEq)]
+#[derive(Component, Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum GameState {
     #[default]
     Playing,
-    Paused,
+    /// Paused since the given real time, which does not count toward the
+    /// trial.
+    Paused { since: Duration },
+}
+
+/// Whether the game is being played rather than paused.
+pub fn playing(query: Query<&GameState>) -> bool {
+    query.get_single().ok() == Some(&GameState::Playing)
 }
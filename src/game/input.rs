@@ -2,26 +2,124 @@ use bevy::prelude::*;
 
 use crate::state::AppState;
 
-use super::core::round::Answer;
+use super::{
+    core::{cue::CueTimer, state::playing, GameSession},
+    modality::Modalities,
+    settings::{GameMode, GameSettings},
+    TrialSet,
+};
 
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, input_system.run_if(in_state(AppState::Game)));
+        app.add_systems(OnEnter(AppState::Game), reset_typed_answer)
+            .add_systems(
+                Update,
+                (
+                    input_system,
+                    arithmetic_input_system.run_if(arithmetic_mode),
+                )
+                    .in_set(TrialSet::Answer)
+                    .run_if(in_state(AppState::Game).and_then(playing)),
+            );
     }
 }
 
-fn input_system(keyboard_input: Res<ButtonInput<KeyCode>>, mut answer: ResMut<Answer>) {
-    if keyboard_input.pressed(KeyCode::KeyA) {
-        answer.position = true;
+/// Answer the matches of the modalities whose key was pressed, timed from
+/// the onset of the cues.
+fn input_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    modalities: Res<Modalities>,
+    mut query: Query<(&mut GameSession, &CueTimer)>,
+) {
+    if let Ok((mut session, timer)) = query.get_single_mut() {
+        for modality in modalities.iter() {
+            if keyboard_input.just_pressed(modality.key) {
+                session.answer_match(modality.id, timer.elapsed_secs());
+            }
+        }
+    }
+}
+
+/// Result typed by the player in the current round of an arithmetic game.
+#[derive(Resource, Debug, Default)]
+pub struct TypedAnswer(String);
+
+impl TypedAnswer {
+    /// Longest result that can be typed, sign included.
+    const MAX_LEN: usize = 4;
+
+    pub fn push_digit(&mut self, digit: u32) {
+        if self.0.len() < Self::MAX_LEN {
+            self.0.extend(char::from_digit(digit, 10));
+        }
+    }
+
+    pub fn toggle_sign(&mut self) {
+        match self.0.strip_prefix('-') {
+            Some(digits) => self.0 = digits.to_string(),
+            None => self.0.insert(0, '-'),
+        }
+    }
+
+    pub fn pop(&mut self) {
+        self.0.pop();
+    }
+
+    pub fn value(&self) -> Option<i32> {
+        self.0.parse().ok()
     }
 
-    if keyboard_input.pressed(KeyCode::KeyS) {
-        answer.sound = true;
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
 
-    if keyboard_input.pressed(KeyCode::KeyD) {
-        answer.color = true;
+    pub fn reset(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Forget the digits typed in a game left before the end of its round.
+fn reset_typed_answer(mut typed: ResMut<TypedAnswer>) {
+    typed.reset();
+}
+
+fn arithmetic_mode(settings: Res<GameSettings>) -> bool {
+    settings.mode == GameMode::Arithmetic
+}
+
+/// Type the result of an arithmetic round on the keyboard or the keypad.
+fn arithmetic_input_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut typed: ResMut<TypedAnswer>,
+) {
+    for key in keyboard_input.get_just_pressed() {
+        match key {
+            KeyCode::Minus | KeyCode::NumpadSubtract => typed.toggle_sign(),
+            KeyCode::Backspace => typed.pop(),
+            key => {
+                if let Some(digit) = digit(key) {
+                    typed.push_digit(digit);
+                }
+            }
+        }
+    }
+}
+
+/// Digit typed with `key`, on the keyboard or the keypad.
+pub fn digit(key: &KeyCode) -> Option<u32> {
+    match key {
+        KeyCode::Digit0 | KeyCode::Numpad0 => Some(0),
+        KeyCode::Digit1 | KeyCode::Numpad1 => Some(1),
+        KeyCode::Digit2 | KeyCode::Numpad2 => Some(2),
+        KeyCode::Digit3 | KeyCode::Numpad3 => Some(3),
+        KeyCode::Digit4 | KeyCode::Numpad4 => Some(4),
+        KeyCode::Digit5 | KeyCode::Numpad5 => Some(5),
+        KeyCode::Digit6 | KeyCode::Numpad6 => Some(6),
+        KeyCode::Digit7 | KeyCode::Numpad7 => Some(7),
+        KeyCode::Digit8 | KeyCode::Numpad8 => Some(8),
+        KeyCode::Digit9 | KeyCode::Numpad9 => Some(9),
+        _ => None,
     }
 }
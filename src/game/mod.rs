@@ -1,6 +1,10 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
+use nback_core::{
+    log::SessionLog,
+    session::{Session, TrialResult},
+};
 
 use crate::{
     config,
@@ -8,22 +12,26 @@ use crate::{
 };
 
 use self::{
+    combination::CombinationPlugin,
     core::{
-        cue::{CueEngine, CueTimer},
-        round::{Answer, Round},
-        score::Score,
-        state::GameState,
-        DualNBackBundle,
+        cue::{CueTimer, TrialTiming},
+        state::{playing, GameState},
+        DualNBackBundle, GameSession,
     },
-    input::InputPlugin,
-    score::{GameScore, LatestGameScores},
-    settings::GameSettings,
-    tile::{color::TileColor, position::TilePosition, sound::TileSound, TileBundle, TilePlugin},
+    input::{InputPlugin, TypedAnswer},
+    modality::Modalities,
+    pause::PausePlugin,
+    score::{GameScore, LatestGameScores, ModalityScore},
+    settings::{GameMode, GameSettings},
+    tile::{color::TileColor, Tile, TileBundle, TilePlugin},
     ui::{button::GameButtonPlugin, UiPlugin},
 };
 
+pub mod combination;
 pub mod core;
 pub mod input;
+pub mod modality;
+pub mod pause;
 pub mod score;
 pub mod settings;
 pub mod tile;
@@ -33,12 +41,19 @@ pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Answer::default())
+        app.insert_resource(TypedAnswer::default())
             .add_plugins(UiPlugin)
             .add_plugins(TilePlugin)
+            .add_plugins(CombinationPlugin)
             .add_plugins(InputPlugin)
             .add_plugins(GameButtonPlugin)
+            .add_plugins(PausePlugin)
+            .add_event::<StartOfRoundEvent>()
             .add_event::<EndOfRoundEvent>()
+            .configure_sets(
+                Update,
+                (TrialSet::Answer, TrialSet::Tick, TrialSet::End).chain(),
+            )
             .add_systems(OnEnter(AppState::Game), setup)
             .add_systems(
                 PreUpdate,
@@ -46,7 +61,12 @@ impl Plugin for GamePlugin {
             )
             .add_systems(
                 Update,
-                (timer_system, end_of_round_system).run_if(in_state(AppState::Game)),
+                (
+                    timer_system.in_set(TrialSet::Tick),
+                    stimulus_system.after(TrialSet::Tick),
+                    end_of_round_system.in_set(TrialSet::End).run_if(playing),
+                )
+                    .run_if(in_state(AppState::Game)),
             )
             .add_systems(OnExit(AppState::Game), despawn_screen::<OnGameScreen>);
     }
@@ -55,10 +75,12 @@ impl Plugin for GamePlugin {
 fn setup(
     mut commands: Commands,
     settings: Res<GameSettings>,
+    modalities: Res<Modalities>,
     mut animations: ResMut<Assets<AnimationClip>>,
+    mut events: EventWriter<StartOfRoundEvent>,
 ) {
     // Add walls
-    let edge = (config::TILE_SIZE * 3.0) + (config::TILE_SPACING * 4.0);
+    let edge = settings.grid.edge();
     let bounds = Vec2::new(edge, edge);
     // left
     commands.spawn((
@@ -139,36 +161,77 @@ fn setup(
         },
     );
 
-    // Create the animation player, and set it to repeat
-    let mut player = AnimationPlayer::default();
-    player.play(animations.add(animation));
+    // Create an animation player for every tile
+    let animation = animations.add(animation);
+    let player = || {
+        let mut player = AnimationPlayer::default();
+        player.play(animation.clone());
+        player
+    };
 
-    // start with a cue
-    let mut timer = CueTimer::with_duration(settings.round_time);
-    timer.tick(Duration::from_millis(
-        ((settings.round_time * 1000.0) as u64) - 1,
-    ));
+    // the session draws the cues of the first trial, shown right away
+    let timing = TrialTiming::new(
+        settings.stimulus_time,
+        settings.interval_time,
+        settings.jitter,
+        settings.seed,
+    );
+    let timer = CueTimer::with_duration(timing.duration());
+    events.send(StartOfRoundEvent(0));
+
+    // a loaded plan is played as is, all of its trials and no more, unless
+    // it does not fit this game
+    let (engine, rounds) = match settings.plan.as_ref().map(|plan| {
+        (
+            plan.engine(&modalities.symbols(&settings)),
+            plan.trials.len(),
+        )
+    }) {
+        Some((Ok(engine), rounds)) => (engine, rounds),
+        Some((Err(err), _)) => {
+            error!(%err, "cannot play the plan, drawing the cues instead");
+            (settings.engine(&modalities), settings.rounds)
+        }
+        None => (settings.engine(&modalities), settings.rounds),
+    };
 
     // game
-    commands.spawn((
-        TileBundle {
-            name: tile,
-            animation: player,
-            ..default()
-        },
-        DualNBackBundle {
-            engine: CueEngine::with(
-                settings.n,
-                settings.position,
-                settings.color,
-                settings.sound,
-            ),
-            round: Round::with_total(settings.rounds),
-            timer,
-            ..default()
-        },
-        OnGameScreen,
-    ));
+    let first = commands
+        .spawn((
+            TileBundle {
+                name: tile.clone(),
+                animation: player(),
+                ..default()
+            },
+            DualNBackBundle {
+                session: GameSession(Session::new(engine, rounds, settings.task())),
+                timer,
+                ..default()
+            },
+            timing,
+            OnGameScreen,
+        ))
+        .id();
+
+    // the tiles of multi-stimulus games are told apart by their color
+    if settings.mode == GameMode::NBack && settings.tiles > 1 {
+        for (i, color) in TileColor::ALL.iter().enumerate().take(settings.tiles) {
+            if i == 0 {
+                commands.entity(first).insert(color.clone());
+            } else {
+                commands.spawn((
+                    TileBundle {
+                        tile: Tile(i),
+                        name: tile.clone(),
+                        animation: player(),
+                        ..default()
+                    },
+                    color.clone(),
+                    OnGameScreen,
+                ));
+            }
+        }
+    }
 }
 
 /// Tick all the `CueTimer` components on entities within the scene using bevy's
@@ -184,88 +247,66 @@ fn timer_system(time: Res<Time>, mut query: Query<(&mut CueTimer, &GameState)>)
     }
 }
 
-#[derive(Event)]
-pub struct EndOfRoundEvent {
-    pub round: usize,
+/// Show the tiles at the start of every trial and hide them once the cues
+/// have been shown for `GameSettings::stimulus_time`.
+fn stimulus_system(
+    settings: Res<GameSettings>,
+    timer_query: Query<&CueTimer>,
+    mut query: Query<&mut Visibility, With<Tile>>,
+) {
+    if let Ok(timer) = timer_query.get_single() {
+        let visibility = if timer.elapsed_secs() < settings.stimulus_time {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        for mut tile in &mut query {
+            tile.set_if_neq(visibility);
+        }
+    }
+}
+
+/// Steps of every frame of a trial, in order: the answers given during the
+/// frame are recorded before the trial they answer may end.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TrialSet {
+    /// Record the answers of the player.
+    Answer,
+    /// Advance the time of the trial.
+    Tick,
+    /// Score the trial once its time is up.
+    End,
 }
 
+/// Index of the round whose cues were just drawn.
+#[derive(Event, Deref)]
+pub struct StartOfRoundEvent(pub usize);
+
+/// Scored responses to the round that just ended.
+#[derive(Event, Deref)]
+pub struct EndOfRoundEvent(pub TrialResult);
+
 fn end_of_round_system(
     mut events: EventWriter<EndOfRoundEvent>,
-    mut answer: ResMut<Answer>,
-    mut query: Query<(
-        &mut CueEngine,
-        &mut Round,
-        &mut Score,
-        &mut TilePosition,
-        &mut TileColor,
-        &mut TileSound,
-        &CueTimer,
-    )>,
+    mut starts: EventWriter<StartOfRoundEvent>,
+    mut typed: ResMut<TypedAnswer>,
+    mut query: Query<(&mut GameSession, &mut CueTimer, &mut TrialTiming)>,
 ) {
-    if let Ok((mut engine, mut round, mut score, mut position, mut color, mut sound, timer)) =
-        query.get_single_mut()
-    {
+    if let Ok((mut session, mut timer, mut timing)) = query.get_single_mut() {
         if timer.just_finished() {
-            if let Some(positions) = &engine.positions {
-                if answer.position {
-                    if positions.is_match() {
-                        score.record_tp();
-                    } else {
-                        score.record_fp();
-                    }
-                } else if positions.is_match() {
-                    score.record_fn();
-                } else {
-                    score.record_tn();
-                }
-            }
-
-            if let Some(colors) = &engine.colors {
-                if answer.color {
-                    if colors.is_match() {
-                        score.record_tp();
-                    } else {
-                        score.record_fp();
-                    }
-                } else if colors.is_match() {
-                    score.record_fn();
-                } else {
-                    score.record_tn();
-                }
-            }
+            session.answer_result(typed.value());
+            typed.reset();
 
-            if let Some(sounds) = &engine.sounds {
-                if answer.sound {
-                    if sounds.is_match() {
-                        score.record_tp();
-                    } else {
-                        score.record_fp();
-                    }
-                } else if sounds.is_match() {
-                    score.record_fn();
-                } else {
-                    score.record_tn();
-                }
-            }
-
-            answer.reset();
+            let (interval, duration) = timing.next_trial();
+            timer.set_duration(Duration::from_secs_f32(duration));
 
-            let (new_position, new_color, new_sound) = engine.new_cue();
-            if let Some(new_position) = new_position {
-                *position = new_position;
-            }
-            if let Some(new_color) = new_color {
-                *color = new_color;
+            let result = session.step(Some(interval));
+            info!(?result, "end of round");
+            let round = result.round + 1;
+            events.send(EndOfRoundEvent(result));
+            if !session.is_over() {
+                starts.send(StartOfRoundEvent(round));
             }
-            if let Some(new_sound) = new_sound {
-                *sound = new_sound;
-            }
-
-            events.send(EndOfRoundEvent {
-                round: round.current,
-            });
-
-            round.current += 1;
         }
     }
 }
@@ -274,27 +315,56 @@ fn end_of_game_system(
     mut settings: ResMut<GameSettings>,
     mut scores: ResMut<LatestGameScores>,
     mut app_state: ResMut<NextState<AppState>>,
-    query: Query<(&CueEngine, &Round, &CueTimer, &mut Score)>,
+    query: Query<(&GameSession, &TrialTiming)>,
 ) {
-    if let Ok((engine, round, timer, score)) = query.get_single() {
-        if round.is_last() {
+    if let Ok((session, timing)) = query.get_single() {
+        if session.is_over() {
+            let (engine, round, score) = (session.engine(), session.round(), session.score());
+
+            match SessionLog::of(session).save(config::LOG_FILE) {
+                Ok(()) => info!(file = config::LOG_FILE, "log saved"),
+                Err(err) => error!(%err, "cannot save the log"),
+            }
+
+            // a planned game is replayed as is
+            let mut level_change = None;
+            if settings.plan.is_none() {
+                // a new game gets a fresh sequence
+                settings.seed = rand::random();
+
+                if let Some(change) = settings.rule.decide(score, session.task()) {
+                    let ids: Vec<_> = engine.levels().into_iter().map(|(id, _)| id).collect();
+                    if settings.move_levels(&ids, change.direction) {
+                        info!(?change, "level change");
+                        level_change = Some(change);
+                    }
+                }
+            }
+
             scores.0.push(GameScore {
-                n: engine.n(),
+                mode: settings.mode,
+                levels: engine.levels(),
+                n_range: engine.n_range(),
                 total_rounds: round.total,
-                round_duration: timer.0.duration().as_secs_f32(),
+                round_duration: timing.mean_duration(),
                 correct: score.correct(),
                 wrong: score.wrong(),
+                lure_false_alarms: score.lure_false_alarms(),
                 f1_score_percent: score.f1_score_percent(),
+                accuracy_percent: score.accuracy_percent(),
+                detection: score.detection(),
+                hit_latency: score.hit_latency(),
+                false_alarm_latency: score.false_alarm_latency(),
+                modalities: score
+                    .modalities()
+                    .map(|(id, score)| ModalityScore::new(id, score))
+                    .collect(),
+                seed: engine.seed(),
+                pauses: session.pauses().to_vec(),
+                trials: session.trials().to_vec(),
+                level_change,
             });
 
-            if score.f1_score_percent() >= 80 {
-                settings.n += 1;
-                settings.set_rounds_from_n();
-            } else if score.f1_score_percent() <= 50 {
-                settings.n = settings.n.max(1);
-                settings.set_rounds_from_n();
-            }
-
             app_state.set(AppState::Menu);
         }
     }
@@ -0,0 +1,224 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use nback_core::cue::{CrossChannel, CueChain, Symbol};
+
+use crate::state::AppState;
+
+use super::{
+    core::GameSession,
+    end_of_round_system,
+    settings::{CueSettings, GameSettings},
+    tile::Tile,
+    StartOfRoundEvent,
+};
+
+pub use nback_core::modality::ModalityId;
+
+/// A stream of cues the player has to match N back.
+///
+/// A modality owns everything specific to its cues: how many distinct cues
+/// are drawn, how a cue is presented, its label and the key answering a
+/// match. Adding a [`ModalityPlugin`] for it is all it takes to play it.
+pub trait Modality: Send + Sync + 'static {
+    /// Component presenting a cue on the tile.
+    type Cue: Component;
+
+    const ID: ModalityId;
+    const LABEL: &'static str;
+    /// Short label, unique among the modalities, e.g. `P` for the position.
+    const ABBREV: &'static str;
+    /// Default key answering a match.
+    const KEY: KeyCode;
+    /// Whether the modality is played unless disabled in the menu.
+    const ENABLED: bool = true;
+    /// Number of distinct cues, drawn uniformly.
+    const SYMBOLS: usize;
+    /// Index of the tile presenting the cues.
+    const TILE: usize = 0;
+
+    /// Number of distinct cues with the given settings.
+    fn symbols(_settings: &GameSettings) -> usize {
+        Self::SYMBOLS
+    }
+
+    /// The cue presented for `symbol`.
+    fn cue(symbol: Symbol) -> Self::Cue;
+
+    /// Add the systems rendering or playing `Self::Cue` whenever it changes.
+    fn present(app: &mut App);
+}
+
+/// Description of a registered modality.
+#[derive(Clone, Debug)]
+pub struct ModalityInfo {
+    pub id: ModalityId,
+    pub label: &'static str,
+    /// Short label, unique among the modalities.
+    pub abbrev: &'static str,
+    pub key: KeyCode,
+    /// Index of the tile presenting the cues, only the modalities of the
+    /// first tile are listed in the menu.
+    pub tile: usize,
+    /// Modalities compared by a combination channel, `None` for the
+    /// modalities presenting cues of their own.
+    pub cross: Option<CrossChannel>,
+    symbols: fn(&GameSettings) -> usize,
+}
+
+impl ModalityInfo {
+    /// Number of distinct cues with the given settings.
+    pub fn symbols(&self, settings: &GameSettings) -> usize {
+        (self.symbols)(settings)
+    }
+
+    /// Whether the menu lists the modality: the ones presenting cues of their
+    /// own on the first tile, which the others follow.
+    pub fn is_listed(&self) -> bool {
+        self.tile == 0 && self.cross.is_none()
+    }
+
+    /// Name of the key answering a match, e.g. `A` for `KeyCode::KeyA`.
+    pub fn key_label(&self) -> String {
+        let key = format!("{:?}", self.key);
+        key.strip_prefix("Key")
+            .or_else(|| key.strip_prefix("Digit"))
+            .unwrap_or(&key)
+            .to_string()
+    }
+}
+
+/// Every registered modality, in registration order.
+#[derive(Resource, Default)]
+pub struct Modalities(Vec<ModalityInfo>);
+
+impl Modalities {
+    pub fn iter(&self) -> impl Iterator<Item = &ModalityInfo> {
+        self.0.iter()
+    }
+
+    pub fn get(&self, id: ModalityId) -> Option<&ModalityInfo> {
+        self.iter().find(|modality| modality.id == id)
+    }
+
+    /// Every modality with its number of distinct cues with the given
+    /// settings.
+    pub fn symbols(&self, settings: &GameSettings) -> Vec<(ModalityId, usize)> {
+        self.iter()
+            .map(|modality| (modality.id, modality.symbols(settings)))
+            .collect()
+    }
+
+    pub fn label(&self, id: ModalityId) -> &'static str {
+        self.get(id).map_or(id.0, |modality| modality.label)
+    }
+
+    pub fn abbrev(&self, id: ModalityId) -> &'static str {
+        self.get(id).map_or(id.0, |modality| modality.abbrev)
+    }
+
+    fn push(&mut self, modality: ModalityInfo) {
+        debug_assert!(
+            self.iter()
+                .all(|other| other.id != modality.id && other.abbrev != modality.abbrev),
+            "modality `{}` registered twice or under the short label of another",
+            modality.id.0
+        );
+        self.0.push(modality);
+    }
+}
+
+/// Register a modality and the systems presenting its cues.
+pub struct ModalityPlugin<M>(PhantomData<M>);
+
+impl<M> Default for ModalityPlugin<M> {
+    fn default() -> Self {
+        ModalityPlugin(PhantomData)
+    }
+}
+
+impl<M: Modality> Plugin for ModalityPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Modalities>()
+            .world
+            .resource_mut::<Modalities>()
+            .push(ModalityInfo {
+                id: M::ID,
+                label: M::LABEL,
+                abbrev: M::ABBREV,
+                key: M::KEY,
+                tile: M::TILE,
+                cross: None,
+                symbols: M::symbols,
+            });
+
+        // the other tiles follow the settings of the first one
+        if M::TILE == 0 {
+            app.world
+                .get_resource_or_insert_with(GameSettings::default)
+                .register(
+                    M::ID,
+                    CueSettings {
+                        enabled: M::ENABLED,
+                        ..default()
+                    },
+                );
+        }
+
+        app.add_systems(
+            Update,
+            cue_system::<M>
+                .after(end_of_round_system)
+                .run_if(in_state(AppState::Game)),
+        );
+
+        M::present(app);
+    }
+}
+
+/// Register a combination channel, answered like a modality but comparing
+/// the cues of two modalities, see [`CrossChannel`].
+pub struct CrossChannelPlugin {
+    pub id: ModalityId,
+    pub label: &'static str,
+    pub abbrev: &'static str,
+    pub key: KeyCode,
+    pub channel: CrossChannel,
+}
+
+impl Plugin for CrossChannelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Modalities>()
+            .world
+            .resource_mut::<Modalities>()
+            .push(ModalityInfo {
+                id: self.id,
+                label: self.label,
+                abbrev: self.abbrev,
+                key: self.key,
+                tile: 0,
+                cross: Some(self.channel),
+                symbols: |_| 0,
+            });
+    }
+}
+
+/// Put the latest cue of the modality on its tile at the start of every round.
+fn cue_system<M: Modality>(
+    mut commands: Commands,
+    mut events: EventReader<StartOfRoundEvent>,
+    sessions: Query<&GameSession>,
+    tiles: Query<(Entity, &Tile)>,
+) {
+    for _ in events.read() {
+        let tile = tiles
+            .iter()
+            .find(|(_, tile)| tile.0 == M::TILE)
+            .map(|(entity, _)| entity);
+        if let (Ok(session), Some(tile)) = (sessions.get_single(), tile) {
+            if let Some(symbol) = session.engine().chain(M::ID).and_then(CueChain::latest) {
+                commands.entity(tile).insert(M::cue(symbol));
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use bevy::{prelude::*, ui::FocusPolicy, window::WindowFocused};
+use bevy_kira_audio::prelude::*;
+
+use crate::{
+    palette,
+    state::{AppState, OnGameScreen},
+};
+
+use super::{
+    core::{state::GameState, GameSession},
+    settings::GameSettings,
+    ui::{action_button, button::ButtonAction},
+};
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PauseEvent>()
+            .add_systems(
+                Update,
+                (pause_key_system, focus_system, pause_system)
+                    .chain()
+                    .run_if(in_state(AppState::Game)),
+            )
+            .add_systems(OnExit(AppState::Game), resume_audio)
+            .add_systems(OnEnter(AppState::Restarting), restart);
+    }
+}
+
+/// What the player asks of the game around a pause.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseEvent {
+    Pause,
+    Resume,
+    /// Leave the game and start a new one with the same settings.
+    Restart,
+    /// Leave the game for the menu, without a score.
+    Quit,
+}
+
+/// Tag component of the overlay shown while the game is paused.
+#[derive(Component)]
+pub struct PauseOverlay;
+
+/// Pause or resume with Escape, and restart or quit from the pause with R
+/// or Q.
+fn pause_key_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut events: EventWriter<PauseEvent>,
+    query: Query<&GameState>,
+) {
+    let Ok(state) = query.get_single() else {
+        return;
+    };
+    let paused = *state != GameState::Playing;
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        events.send(if paused {
+            PauseEvent::Resume
+        } else {
+            PauseEvent::Pause
+        });
+    } else if paused && keyboard_input.just_pressed(KeyCode::KeyR) {
+        events.send(PauseEvent::Restart);
+    } else if paused && keyboard_input.just_pressed(KeyCode::KeyQ) {
+        events.send(PauseEvent::Quit);
+    }
+}
+
+/// Pause the game when its window loses the focus, since the player is
+/// obviously not playing.
+fn focus_system(mut focus: EventReader<WindowFocused>, mut events: EventWriter<PauseEvent>) {
+    for event in focus.read() {
+        if !event.focused {
+            events.send(PauseEvent::Pause);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pause_system(
+    mut commands: Commands,
+    mut events: EventReader<PauseEvent>,
+    mut app_state: ResMut<NextState<AppState>>,
+    time: Res<Time<Real>>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(&mut GameState, &mut GameSession)>,
+    overlays: Query<Entity, With<PauseOverlay>>,
+) {
+    let Ok((mut state, mut session)) = query.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        match (*event, *state) {
+            (PauseEvent::Pause, GameState::Playing) => {
+                info!("pause");
+                *state = GameState::Paused {
+                    since: time.elapsed(),
+                };
+                audio.pause();
+                overlay(&mut commands, &asset_server);
+            }
+            (PauseEvent::Resume, GameState::Paused { since }) => {
+                let duration = (time.elapsed() - since).as_secs_f32();
+                info!(duration, "resume");
+                session.record_pause(duration);
+                *state = GameState::Playing;
+                audio.resume();
+                for overlay in &overlays {
+                    commands.entity(overlay).despawn_recursive();
+                }
+            }
+            (PauseEvent::Restart, _) => app_state.set(AppState::Restarting),
+            (PauseEvent::Quit, _) => app_state.set(AppState::Menu),
+            _ => {}
+        }
+    }
+}
+
+/// Sounds paused with the game must not stay paused once it is left.
+fn resume_audio(audio: Res<Audio>) {
+    audio.resume();
+}
+
+/// Start the game over, with fresh cues unless a plan is played.
+fn restart(mut settings: ResMut<GameSettings>, mut app_state: ResMut<NextState<AppState>>) {
+    if settings.plan.is_none() {
+        settings.seed = rand::random();
+    }
+    app_state.set(AppState::Game);
+}
+
+/// Cover the game, so that the cues cannot be studied during the pause.
+fn overlay(commands: &mut Commands, asset_server: &AssetServer) {
+    let font = asset_server.load("embedded://fonts/FiraSans-Bold.ttf");
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: palette::SLATE_800.into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(1),
+                ..default()
+            },
+            PauseOverlay,
+            OnGameScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Paused",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 64.0,
+                    color: Color::rgb(0.9, 0.9, 0.9),
+                },
+            ));
+            for (label, key, event) in [
+                ("Resume (Esc)", KeyCode::Escape, PauseEvent::Resume),
+                ("Restart (R)", KeyCode::KeyR, PauseEvent::Restart),
+                ("Quit (Q)", KeyCode::KeyQ, PauseEvent::Quit),
+            ] {
+                action_button(
+                    parent,
+                    label.to_string(),
+                    key,
+                    ButtonAction::Pause(event),
+                    font.clone(),
+                );
+            }
+        });
+}
@@ -1,13 +1,181 @@
 use bevy::prelude::*;
+use nback_core::{
+    progression::{Direction, LevelChange},
+    score::{Detection, Latency, Score},
+    session::{Pause, TrialResult},
+};
+
+use super::{
+    modality::{Modalities, ModalityId},
+    settings::GameMode,
+};
 
 #[derive(Default)]
 pub struct GameScore {
-    pub n: usize,
+    pub mode: GameMode,
+    /// N of every enabled modality.
+    pub levels: Vec<(ModalityId, usize)>,
+    /// Lowest and highest N of the cues of variable-N games.
+    pub n_range: Option<(usize, usize)>,
     pub total_rounds: usize,
+    /// Mean duration of a trial, in seconds.
     pub round_duration: f32,
     pub correct: usize,
     pub wrong: usize,
+    /// Wrong matches answered on lures, also counted in `wrong`.
+    pub lure_false_alarms: usize,
+    pub f1_score_percent: usize,
+    pub accuracy_percent: usize,
+    /// Signal detection measures over every modality, `None` without both
+    /// matches and other cues.
+    pub detection: Option<Detection>,
+    /// Latency of the answered matches over every modality.
+    pub hit_latency: Option<Latency>,
+    /// Latency of the false alarms over every modality.
+    pub false_alarm_latency: Option<Latency>,
+    /// Score of every modality and combination channel on its own.
+    pub modalities: Vec<ModalityScore>,
+    /// Seed the cue sequence was generated from.
+    pub seed: u64,
+    /// Pauses taken during the game.
+    pub pauses: Vec<Pause>,
+    /// Cues, answers and outcomes of every trial of the game.
+    pub trials: Vec<TrialResult>,
+    /// Move of the N decided after the game, with its reason.
+    pub level_change: Option<LevelChange>,
+}
+
+impl GameScore {
+    /// Score of the game in its mode: the F1 score of n-back games and the
+    /// accuracy of arithmetic ones.
+    pub fn score_percent(&self) -> usize {
+        match self.mode {
+            GameMode::NBack | GameMode::Combination => self.f1_score_percent,
+            GameMode::Arithmetic => self.accuracy_percent,
+        }
+    }
+
+    /// Short description of the levels, e.g. `2` when all modalities share
+    /// the same N, `P3 C2 Sh2` otherwise, or `1-3` for the range of N of a
+    /// variable-N game.
+    pub fn levels_label(&self, modalities: &Modalities) -> String {
+        if let Some((min, max)) = self.n_range {
+            return format!("{}-{}", min, max);
+        }
+
+        match self.levels.as_slice() {
+            [] => String::new(),
+            [(_, n), rest @ ..] if rest.iter().all(|(_, m)| m == n) => n.to_string(),
+            levels => levels
+                .iter()
+                .map(|(id, n)| format!("{}{}", modalities.abbrev(*id), n))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    /// Move of the N after the game and its reason, e.g.
+    /// `N+1: 85%, at least 80%`, empty if the N stayed.
+    pub fn level_change_label(&self) -> String {
+        self.level_change
+            .map(|change| {
+                let sign = match change.direction {
+                    Direction::Up => '+',
+                    Direction::Down => '-',
+                };
+                format!("N{}1: {}", sign, change.reason)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Sensitivity over every modality, e.g. `d′ 1.85`, empty without
+    /// both matches and other cues.
+    pub fn detection_label(&self) -> String {
+        self.detection.map(d_prime_label).unwrap_or_default()
+    }
+
+    /// Seed the cues of the game were drawn from, e.g. `seed 42`.
+    pub fn seed_label(&self) -> String {
+        format!("seed {}", self.seed)
+    }
+
+    /// Median latency of the answered matches and of the false alarms, e.g.
+    /// `RT 540 ms, 610 ms FA`, empty without any answer.
+    pub fn latency_label(&self) -> String {
+        let hits = self
+            .hit_latency
+            .map(|latency| format!("RT {:.0} ms", latency.median * 1000.0));
+        let false_alarms = self
+            .false_alarm_latency
+            .map(|latency| format!("{:.0} ms FA", latency.median * 1000.0));
+        match (hits, false_alarms) {
+            (Some(hits), Some(false_alarms)) => format!("{}, {}", hits, false_alarms),
+            (Some(hits), None) => hits,
+            (None, Some(false_alarms)) => format!("RT {}", false_alarms),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Accuracy, F1 score and sensitivity of every modality on a line of
+    /// its own, e.g. `Position 92% F1 80% d′ 2.10`, empty when a single
+    /// modality was played.
+    pub fn modalities_label(&self, modalities: &Modalities) -> String {
+        if self.modalities.len() < 2 {
+            return String::new();
+        }
+
+        self.modalities
+            .iter()
+            .map(|score| {
+                let mut label = format!(
+                    "{} {}% F1 {}%",
+                    modalities.label(score.id),
+                    score.accuracy_percent,
+                    score.f1_score_percent
+                );
+                if let Some(detection) = score.detection {
+                    label.push(' ');
+                    label.push_str(&d_prime_label(detection));
+                }
+                label
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Score of a modality or combination channel on its own.
+#[derive(Clone, Debug)]
+pub struct ModalityScore {
+    pub id: ModalityId,
+    pub correct: usize,
+    pub wrong: usize,
+    pub lure_false_alarms: usize,
     pub f1_score_percent: usize,
+    pub accuracy_percent: usize,
+    pub detection: Option<Detection>,
+    pub hit_latency: Option<Latency>,
+    pub false_alarm_latency: Option<Latency>,
+}
+
+impl ModalityScore {
+    pub fn new(id: ModalityId, score: &Score) -> Self {
+        ModalityScore {
+            id,
+            correct: score.correct(),
+            wrong: score.wrong(),
+            lure_false_alarms: score.lure_false_alarms(),
+            f1_score_percent: score.f1_score_percent(),
+            accuracy_percent: score.accuracy_percent(),
+            detection: score.detection(),
+            hit_latency: score.hit_latency(),
+            false_alarm_latency: score.false_alarm_latency(),
+        }
+    }
+}
+
+fn d_prime_label(detection: Detection) -> String {
+    format!("d′ {:.2}", detection.d_prime)
 }
 
 #[derive(Default, Resource)]
@@ -1,30 +1,341 @@
 use bevy::prelude::*;
+use nback_core::{
+    arithmetic::Operation,
+    block::MatchQuota,
+    cue::{CrossChannel, CueEngine, TargetOffset},
+    jitter::Jitter,
+    plan::SessionPlan,
+    progression::{Direction, Progression, ProgressionRule},
+    session::Task,
+};
+
+pub use nback_core::cue::CueSettings;
+
+use super::{
+    modality::{Modalities, Modality, ModalityId},
+    tile::{
+        letter::LetterModality,
+        number::NumberModality,
+        pan::Stereo,
+        pitch::Scale,
+        position::{Grid, POSITION_IDS},
+        sound::SoundModality,
+    },
+};
 
 #[derive(Resource)]
 pub struct GameSettings {
-    pub n: usize,
     pub rounds: usize,
-    pub round_time: f32,
-    pub position: bool,
-    pub color: bool,
-    pub sound: bool,
+    /// How long the cues of every trial are shown, in seconds.
+    pub stimulus_time: f32,
+    /// How long the tile stays hidden between the cues of two trials, in
+    /// seconds.
+    pub interval_time: f32,
+    /// How the interval varies from trial to trial.
+    pub jitter: Jitter,
+    /// Settings of every registered modality, in registration order.
+    pub cues: Vec<(ModalityId, CueSettings)>,
+    /// Seed of the cue sequence of the next game.
+    pub seed: u64,
+    /// Exact number of matches of a game, `None` draws matches at random.
+    pub quota: Option<MatchQuota>,
+    pub mode: GameMode,
+    /// Operation of arithmetic games.
+    pub operation: Operation,
+    /// Grid of the position modality.
+    pub grid: Grid,
+    /// Scale of the tones of the pitch modality.
+    pub scale: Scale,
+    /// Stereo positions of the sound position modality.
+    pub stereo: Stereo,
+    /// Number of tiles shown at once.
+    pub tiles: usize,
+    /// Whether every cue is compared with a different N, up to the N of its
+    /// modality.
+    pub variable_n: bool,
+    /// How far back the cue compared with a new one is.
+    pub target: TargetOffset,
+    /// Plan played by the next games instead of drawing their cues.
+    pub plan: Option<SessionPlan>,
+    /// Progression moving the N between games.
+    pub progression: Progression,
+    /// Rule of the progression, with the games it has seen.
+    pub rule: Box<dyn ProgressionRule>,
 }
 
 impl GameSettings {
+    /// Add the settings of a modality, unless it already has some.
+    pub fn register(&mut self, id: ModalityId, settings: CueSettings) {
+        if self.cue(id).is_none() {
+            self.cues.push((id, settings));
+        }
+    }
+
+    pub fn cue(&self, id: ModalityId) -> Option<&CueSettings> {
+        self.cues
+            .iter()
+            .find(|(other, _)| *other == id)
+            .map(|(_, settings)| settings)
+    }
+
+    pub fn cue_mut(&mut self, id: ModalityId) -> Option<&mut CueSettings> {
+        self.cues
+            .iter_mut()
+            .find(|(other, _)| *other == id)
+            .map(|(_, settings)| settings)
+    }
+
+    /// Settings of the modalities played in the current mode: the enabled
+    /// ones, the numbers alone in arithmetic games, the letters seen and heard
+    /// in combination games, or the position of every tile and the sound, if
+    /// enabled, when several tiles are shown.
+    pub fn enabled_cues(&self) -> impl Iterator<Item = (ModalityId, &CueSettings)> {
+        let cues: Vec<(ModalityId, &CueSettings)> = match self.mode {
+            GameMode::Arithmetic => self
+                .cue(NumberModality::ID)
+                .map(|cue| (NumberModality::ID, cue))
+                .into_iter()
+                .collect(),
+            GameMode::Combination => [LetterModality::ID, SoundModality::ID]
+                .into_iter()
+                .filter_map(|id| self.cue(id).map(|cue| (id, cue)))
+                .collect(),
+            GameMode::NBack if self.tiles > 1 => {
+                let position = self.cue(POSITION_IDS[0]);
+                let sound = self.cue(SoundModality::ID).filter(|cue| cue.enabled);
+                POSITION_IDS
+                    .iter()
+                    .take(self.tiles)
+                    .filter_map(|id| position.map(|cue| (*id, cue)))
+                    .chain(sound.map(|cue| (SoundModality::ID, cue)))
+                    .collect()
+            }
+            GameMode::NBack => self
+                .cues
+                .iter()
+                .filter(|(_, settings)| settings.enabled)
+                .map(|(id, settings)| (*id, settings))
+                .collect(),
+        };
+        cues.into_iter()
+    }
+
+    /// Channels comparing the modalities with each other in the current
+    /// mode, only played in combination games.
+    pub fn crosses(&self, modalities: &Modalities) -> Vec<(ModalityId, CrossChannel)> {
+        match self.mode {
+            GameMode::Combination => modalities
+                .iter()
+                .filter_map(|modality| modality.cross.map(|cross| (modality.id, cross)))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// What the player answers in every trial of the current mode.
+    pub fn task(&self) -> Task {
+        match self.mode {
+            GameMode::NBack | GameMode::Combination => Task::Match,
+            GameMode::Arithmetic => Task::Arithmetic(self.operation),
+        }
+    }
+
+    /// Highest N among the enabled modalities.
+    pub fn max_n(&self) -> usize {
+        self.enabled_cues()
+            .map(|(_, settings)| settings.n)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Durations offered by the menu for the cues, in seconds.
+    pub const STIMULUS_TIMES: [f32; 4] = [0.5, 1.0, 2.0, 3.0];
+    /// Durations offered by the menu between the cues, in seconds.
+    pub const INTERVAL_TIMES: [f32; 5] = [0.0, 1.0, 1.5, 2.5, 3.0];
+    /// Lure rates offered by the menu for every modality.
+    pub const LURE_RATES: [f64; 4] = [0.0, 0.1, CueSettings::LURE_RATE, 0.3];
+
+    /// Duration of every trial, the cues and the blank interval after them.
+    pub fn round_time(&self) -> f32 {
+        self.stimulus_time + self.interval_time
+    }
+
+    /// Show the cues for the duration following the current one in
+    /// `GameSettings::STIMULUS_TIMES`, wrapping around.
+    pub fn cycle_stimulus_time(&mut self) {
+        self.stimulus_time = next_value(&Self::STIMULUS_TIMES, self.stimulus_time);
+    }
+
+    /// Hide the tile for the duration following the current one in
+    /// `GameSettings::INTERVAL_TIMES`, wrapping around.
+    pub fn cycle_interval_time(&mut self) {
+        self.interval_time = next_value(&Self::INTERVAL_TIMES, self.interval_time);
+    }
+
+    /// Draw lures for the modality `id` at the rate following its current one
+    /// in `GameSettings::LURE_RATES`, wrapping around.
+    pub fn cycle_lure_rate(&mut self, id: ModalityId) {
+        if let Some(cue) = self.cue_mut(id) {
+            cue.lure_rate = next_value(&Self::LURE_RATES, cue.lure_rate);
+        }
+    }
+
+    /// Switch to the progression following the current one, starting its
+    /// rule afresh.
+    pub fn cycle_progression(&mut self) {
+        self.progression = self.progression.next();
+        self.rule = self.progression.rule();
+    }
+
+    /// Move the N of the modalities one step, never under 1, returning
+    /// whether any N moved.
+    pub fn move_levels(&mut self, ids: &[ModalityId], direction: Direction) -> bool {
+        let mut moved = false;
+        for id in ids {
+            if let Some(cue) = self.cue_mut(*id) {
+                let n = match direction {
+                    Direction::Up => cue.n + 1,
+                    Direction::Down => cue.n.saturating_sub(1).max(1),
+                };
+                moved |= n != cue.n;
+                cue.n = n;
+            }
+        }
+        if moved {
+            self.set_rounds_from_n();
+        }
+        moved
+    }
+
     pub fn set_rounds_from_n(&mut self) {
-        self.rounds = 20 + self.n.pow(2);
+        self.rounds = 20 + self.max_n().pow(2);
+    }
+
+    /// Engine drawing the cues of a game played with these settings.
+    pub fn engine(&self, modalities: &Modalities) -> CueEngine {
+        let mut engine = CueEngine::with_seed(self.seed);
+        for (id, cue) in self.enabled_cues() {
+            if let Some(modality) = modalities.get(id) {
+                engine = engine.with_chain(id, modality.symbols(self), cue);
+            }
+        }
+        if self.tiles > 1 {
+            engine = engine.with_distinct(POSITION_IDS);
+        }
+        if self.variable_n {
+            engine = engine.with_variable_n();
+        }
+        engine = engine.with_target(self.target);
+        if self.mode == GameMode::Arithmetic {
+            engine = engine.with_rule(NumberModality::ID, self.operation.rule());
+        }
+        if let Some(quota) = self.quota {
+            engine = engine.with_quota(quota, self.rounds);
+        }
+        for (id, channel) in self.crosses(modalities) {
+            engine = engine.with_cross(id, channel);
+        }
+        engine
+    }
+
+    /// Play the plan in the next games, with the settings it was made with.
+    pub fn apply_plan(&mut self, plan: SessionPlan) {
+        self.rounds = plan.trials.len();
+        self.stimulus_time = plan
+            .stimulus_time
+            .unwrap_or(plan.round_time)
+            .min(plan.round_time);
+        self.interval_time = plan.round_time - self.stimulus_time;
+        self.seed = plan.seed;
+        (self.mode, self.operation) = match plan.task {
+            Task::Match if !plan.crosses.is_empty() => (GameMode::Combination, self.operation),
+            Task::Match => (GameMode::NBack, self.operation),
+            Task::Arithmetic(operation) => (GameMode::Arithmetic, operation),
+        };
+        self.target = plan.target;
+        self.variable_n = plan.is_variable_n();
+        self.quota = None;
+
+        let planned = |id: ModalityId| plan.modalities.iter().find(|m| m.id == id.0);
+        for (id, cue) in self.cues.iter_mut() {
+            let modality = planned(*id);
+            cue.enabled = modality.is_some();
+            if let Some(modality) = modality {
+                cue.n = modality.n;
+                cue.lure_rate = modality.lure_rate;
+            }
+        }
+
+        let positions: Vec<_> = POSITION_IDS.iter().filter_map(|id| planned(*id)).collect();
+        self.tiles = positions.len().max(1);
+        if let Some(size) = positions.first().and_then(|position| {
+            Grid::SIZES
+                .into_iter()
+                .find(|size| size * size == position.symbols)
+        }) {
+            self.grid = Grid { size };
+        }
+        if self.tiles > 1 {
+            if let Some(cue) = self.cue_mut(POSITION_IDS[0]) {
+                cue.enabled = true;
+            }
+        }
+
+        self.plan = Some(plan);
     }
 }
 
 impl Default for GameSettings {
     fn default() -> Self {
         Self {
-            n: 2,
             rounds: 24,
-            round_time: 3.0,
-            position: true,
-            color: true,
-            sound: true,
+            stimulus_time: 0.5,
+            interval_time: 2.5,
+            jitter: Jitter::default(),
+            cues: Vec::new(),
+            seed: rand::random(),
+            quota: None,
+            mode: GameMode::default(),
+            operation: Operation::default(),
+            grid: Grid::default(),
+            scale: Scale::default(),
+            stereo: Stereo::default(),
+            tiles: 1,
+            variable_n: false,
+            target: TargetOffset::default(),
+            plan: None,
+            progression: Progression::default(),
+            rule: Progression::default().rule(),
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    /// Answer which modalities match the cue N back.
+    #[default]
+    NBack,
+    /// Type the result of the operation between the number shown and the one
+    /// N back.
+    Arithmetic,
+    /// Answer which of the letters seen and heard match the letters seen and
+    /// heard N back, across the two as well.
+    Combination,
+}
+
+/// The value following `current` in `values`, the first one if `current` is
+/// not among them.
+fn next_value<T: Copy + PartialEq>(values: &[T], current: T) -> T {
+    let i = values.iter().position(|value| *value == current);
+    values[i.map_or(0, |i| (i + 1) % values.len())]
+}
+
+/// Label of a duration in seconds, as shown by the menu.
+pub fn time_label(time: f32) -> String {
+    format!("{:.1}s", time)
+}
+
+/// Label of a lure rate, as shown by the menu.
+pub fn lure_label(rate: f64) -> String {
+    format!("{}% lures", (rate * 100.0).round())
+}
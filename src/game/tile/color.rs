@@ -1,10 +1,11 @@
 use bevy::prelude::*;
-use rand::{
-    distributions::{Distribution, Standard},
-    Rng,
-};
+use nback_core::cue::Symbol;
 
-use crate::palette;
+use crate::{
+    game::modality::{Modality, ModalityId},
+    palette,
+    state::AppState,
+};
 
 #[derive(Component, Clone, Debug, Default, PartialEq)]
 pub enum TileColor {
@@ -17,16 +18,14 @@ pub enum TileColor {
     None,
 }
 
-impl Distribution<TileColor> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TileColor {
-        match rng.gen_range(0..=5) {
-            0 => TileColor::A,
-            1 => TileColor::B,
-            2 => TileColor::C,
-            3 => TileColor::D,
-            _ => TileColor::E,
-        }
-    }
+impl TileColor {
+    pub const ALL: [TileColor; 5] = [
+        TileColor::A,
+        TileColor::B,
+        TileColor::C,
+        TileColor::D,
+        TileColor::E,
+    ];
 }
 
 impl From<&TileColor> for Color {
@@ -41,3 +40,32 @@ impl From<&TileColor> for Color {
         }
     }
 }
+
+/// Color of the tile.
+pub struct ColorModality;
+
+impl Modality for ColorModality {
+    type Cue = TileColor;
+
+    const ID: ModalityId = ModalityId("color");
+    const LABEL: &'static str = "Color";
+    const ABBREV: &'static str = "C";
+    const KEY: KeyCode = KeyCode::KeyD;
+    const SYMBOLS: usize = TileColor::ALL.len();
+
+    fn cue(symbol: Symbol) -> TileColor {
+        TileColor::ALL[symbol].clone()
+    }
+
+    fn present(app: &mut App) {
+        app.add_systems(Update, tile_color_system.run_if(in_state(AppState::Game)));
+    }
+}
+
+/// Update tile state every time the color changes.
+pub fn tile_color_system(mut query: Query<(&mut Sprite, &TileColor), Changed<TileColor>>) {
+    for (mut sprite, color) in &mut query {
+        info!(?color, "tile updated");
+        sprite.color = color.into();
+    }
+}
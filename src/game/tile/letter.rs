@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use nback_core::cue::Symbol;
+
+use crate::{
+    game::modality::{Modality, ModalityId},
+    state::AppState,
+};
+
+use super::{
+    sound::TileSound,
+    text::{tile_text_system, TileText},
+};
+
+/// Letter written on the tile, from the letters read aloud.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct TileLetter(pub Option<char>);
+
+/// The letter seen on the tile, compared with the letter heard in
+/// combination games.
+pub struct LetterModality;
+
+impl Modality for LetterModality {
+    type Cue = TileLetter;
+
+    const ID: ModalityId = ModalityId("letter");
+    const LABEL: &'static str = "Letter";
+    const ABBREV: &'static str = "L";
+    const KEY: KeyCode = KeyCode::KeyV;
+    const ENABLED: bool = false;
+    const SYMBOLS: usize = TileSound::ALL.len();
+
+    fn cue(symbol: Symbol) -> TileLetter {
+        TileLetter(TileSound::ALL[symbol].letter())
+    }
+
+    fn present(app: &mut App) {
+        app.add_systems(
+            Update,
+            tile_text_system::<TileLetter>.run_if(in_state(AppState::Game)),
+        );
+    }
+}
+
+impl TileText for TileLetter {
+    fn text(&self) -> String {
+        self.0.map(String::from).unwrap_or_default()
+    }
+}
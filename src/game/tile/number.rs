@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+use nback_core::{
+    arithmetic::{self, NUMBERS},
+    cue::Symbol,
+};
+
+use crate::{
+    game::modality::{Modality, ModalityId},
+    state::AppState,
+};
+
+use super::text::{tile_text_system, TileText};
+
+/// Number written on the tile.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct TileNumber(pub Option<i32>);
+
+/// Number written on the tile, also the cue of arithmetic games.
+pub struct NumberModality;
+
+impl Modality for NumberModality {
+    type Cue = TileNumber;
+
+    const ID: ModalityId = ModalityId("number");
+    const LABEL: &'static str = "Number";
+    const ABBREV: &'static str = "Nu";
+    const KEY: KeyCode = KeyCode::KeyG;
+    const ENABLED: bool = false;
+    const SYMBOLS: usize = NUMBERS;
+
+    fn cue(symbol: Symbol) -> TileNumber {
+        TileNumber(Some(arithmetic::number(symbol)))
+    }
+
+    fn present(app: &mut App) {
+        app.add_systems(
+            Update,
+            tile_text_system::<TileNumber>.run_if(in_state(AppState::Game)),
+        );
+    }
+}
+
+impl TileText for TileNumber {
+    fn text(&self) -> String {
+        self.0.map(|n| n.to_string()).unwrap_or_default()
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use nback_core::cue::Symbol;
+
+use crate::{
+    asset::AudioAssets,
+    game::{
+        modality::{Modality, ModalityId},
+        settings::GameSettings,
+    },
+    state::AppState,
+};
+
+use super::sound::TileSound;
+
+/// Stereo positions the sounds are panned to, evenly spread from left to
+/// right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stereo {
+    pub positions: usize,
+}
+
+impl Stereo {
+    /// Left, centre and right.
+    pub const DEFAULT: Stereo = Stereo { positions: 3 };
+    /// Numbers of positions offered by the menu.
+    pub const POSITIONS: [usize; 4] = [2, 3, 5, 7];
+
+    /// The stereo following this one in `Stereo::POSITIONS`, wrapping around.
+    pub fn next(&self) -> Stereo {
+        let i = Stereo::POSITIONS
+            .iter()
+            .position(|positions| *positions == self.positions)
+            .unwrap_or(0);
+        Stereo {
+            positions: Stereo::POSITIONS[(i + 1) % Stereo::POSITIONS.len()],
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self.positions {
+            2 => "L R".to_string(),
+            3 => "L C R".to_string(),
+            positions => positions.to_string(),
+        }
+    }
+
+    /// Panning of the position, from 0 hard left to 1 hard right.
+    pub fn panning(&self, pan: &TilePan) -> Option<f64> {
+        pan.0
+            .map(|position| position as f64 / (self.positions.max(2) - 1) as f64)
+    }
+}
+
+impl Default for Stereo {
+    fn default() -> Self {
+        Stereo::DEFAULT
+    }
+}
+
+/// Stereo position the sound of the tile comes from.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct TilePan(pub Option<usize>);
+
+/// Where the letter is heard, from left to right, a second auditory
+/// dimension that needs no screen at all.
+pub struct PanModality;
+
+impl Modality for PanModality {
+    type Cue = TilePan;
+
+    const ID: ModalityId = ModalityId("panning");
+    const LABEL: &'static str = "Panning";
+    const ABBREV: &'static str = "Pa";
+    const KEY: KeyCode = KeyCode::KeyE;
+    const ENABLED: bool = false;
+    const SYMBOLS: usize = Stereo::DEFAULT.positions;
+
+    fn symbols(settings: &GameSettings) -> usize {
+        settings.stereo.positions
+    }
+
+    fn cue(symbol: Symbol) -> TilePan {
+        TilePan(Some(symbol))
+    }
+
+    fn present(app: &mut App) {
+        app.add_systems(Update, tile_pan_system.run_if(in_state(AppState::Game)));
+    }
+}
+
+/// Play a letter from the stereo position of the tile every time it changes,
+/// unless the letters are played as cues of their own, already panned.
+pub fn tile_pan_system(
+    audio: Res<Audio>,
+    audio_assets: Res<AudioAssets>,
+    settings: Res<GameSettings>,
+    query: Query<&TilePan, (Changed<TilePan>, Without<TileSound>)>,
+) {
+    for pan in &query {
+        info!(?pan, "tile updated");
+        if let Some(panning) = settings.stereo.panning(pan) {
+            audio.play(audio_assets.c.clone()).with_panning(panning);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::{f32::consts::TAU, sync::Arc};
+
+use bevy::prelude::*;
+use bevy_kira_audio::{prelude::*, AudioSource};
+use nback_core::cue::Symbol;
+
+use crate::{
+    config,
+    game::{
+        modality::{Modality, ModalityId},
+        settings::GameSettings,
+    },
+    state::AppState,
+};
+
+/// Musical scale the tones are taken from, as semitones above the root.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Scale {
+    #[default]
+    Major,
+    Minor,
+    /// Major pentatonic over two octaves.
+    Pentatonic,
+    WholeTone,
+    Chromatic,
+}
+
+impl Scale {
+    pub const ALL: [Scale; 5] = [
+        Scale::Major,
+        Scale::Minor,
+        Scale::Pentatonic,
+        Scale::WholeTone,
+        Scale::Chromatic,
+    ];
+
+    pub const fn semitones(&self) -> &'static [i32] {
+        match self {
+            Scale::Major => &[0, 2, 4, 5, 7, 9, 11, 12],
+            Scale::Minor => &[0, 2, 3, 5, 7, 8, 10, 12],
+            Scale::Pentatonic => &[0, 2, 4, 7, 9, 12, 14, 16],
+            Scale::WholeTone => &[0, 2, 4, 6, 8, 10, 12],
+            Scale::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+        }
+    }
+
+    /// Frequency of every note of the scale, in Hz.
+    pub fn frequencies(&self) -> impl Iterator<Item = f32> {
+        self.semitones()
+            .iter()
+            .map(|semitone| config::TONE_ROOT * 2f32.powf(*semitone as f32 / 12.0))
+    }
+
+    /// The scale following this one in `Scale::ALL`, wrapping around.
+    pub fn next(&self) -> Scale {
+        let i = Scale::ALL
+            .iter()
+            .position(|scale| scale == self)
+            .unwrap_or(0);
+        Scale::ALL[(i + 1) % Scale::ALL.len()]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Scale::Major => "Major",
+            Scale::Minor => "Minor",
+            Scale::Pentatonic => "Pentatonic",
+            Scale::WholeTone => "Whole tone",
+            Scale::Chromatic => "Chromatic",
+        }
+    }
+}
+
+/// Note of the scale played, as its index in the scale.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct TilePitch(pub Option<usize>);
+
+/// A pure tone from a musical scale, a non-verbal alternative to the letters.
+pub struct PitchModality;
+
+impl Modality for PitchModality {
+    type Cue = TilePitch;
+
+    const ID: ModalityId = ModalityId("pitch");
+    const LABEL: &'static str = "Pitch";
+    const ABBREV: &'static str = "Pi";
+    const KEY: KeyCode = KeyCode::KeyH;
+    const ENABLED: bool = false;
+    const SYMBOLS: usize = Scale::Major.semitones().len();
+
+    fn symbols(settings: &GameSettings) -> usize {
+        settings.scale.semitones().len()
+    }
+
+    fn cue(symbol: Symbol) -> TilePitch {
+        TilePitch(Some(symbol))
+    }
+
+    fn present(app: &mut App) {
+        app.add_systems(OnEnter(AppState::Game), setup)
+            .add_systems(Update, tile_pitch_system.run_if(in_state(AppState::Game)));
+    }
+}
+
+/// Tones of every note of the scale of the current game.
+#[derive(Resource)]
+pub struct ToneAssets(Vec<Handle<AudioSource>>);
+
+fn setup(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    mut sources: ResMut<Assets<AudioSource>>,
+) {
+    let tones = settings
+        .scale
+        .frequencies()
+        .map(|frequency| sources.add(tone(frequency)))
+        .collect();
+    commands.insert_resource(ToneAssets(tones));
+}
+
+/// A sine wave of the given frequency, faded in and out to avoid clicks.
+fn tone(frequency: f32) -> AudioSource {
+    let sample_rate = config::TONE_SAMPLE_RATE;
+    let frames = (config::TONE_DURATION * sample_rate as f32) as usize;
+    let fade = (config::TONE_FADE * sample_rate as f32) as usize;
+
+    let frames: Arc<[Frame]> = (0..frames)
+        .map(|i| {
+            let envelope = (i.min(frames - 1 - i) as f32 / fade as f32).min(1.0);
+            let phase = TAU * frequency * i as f32 / sample_rate as f32;
+            Frame::from_mono(config::TONE_VOLUME * envelope * phase.sin())
+        })
+        .collect();
+
+    AudioSource {
+        sound: StaticSoundData {
+            sample_rate,
+            frames,
+            settings: StaticSoundSettings::default(),
+        },
+    }
+}
+
+/// Play the tone every time the pitch changes.
+pub fn tile_pitch_system(
+    audio: Res<Audio>,
+    tones: Res<ToneAssets>,
+    query: Query<&TilePitch, Changed<TilePitch>>,
+) {
+    for pitch in &query {
+        info!(?pitch, "tile updated");
+        if let Some(tone) = pitch.0.and_then(|note| tones.0.get(note)) {
+            audio.play(tone.clone());
+        }
+    }
+}
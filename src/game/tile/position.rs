@@ -1,70 +1,137 @@
 use bevy::prelude::*;
-use rand::{
-    distributions::{Distribution, Standard},
-    Rng,
-};
+use nback_core::cue::Symbol;
 
-use crate::config;
+use crate::{
+    config,
+    game::{
+        modality::{Modality, ModalityId},
+        settings::GameSettings,
+    },
+    state::AppState,
+};
 
+/// Cell of the grid the tile is on, numbered row by row from the top left
+/// corner. `None` is the center of the grid.
 #[derive(Component, Clone, Debug, Default, PartialEq)]
-pub enum TilePosition {
-    TopLeft,
-    TopCenter,
-    TopRight,
-    CenterLeft,
-    Center,
-    CenterRight,
-    BottomLeft,
-    BottomCenter,
-    BottomRight,
-    #[default]
-    None,
+pub struct TilePosition(pub Option<usize>);
+
+/// Square grid the tile moves on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Grid {
+    /// Number of rows, and of columns.
+    pub size: usize,
 }
 
-impl TilePosition {
-    fn row(&self) -> f32 {
-        match self {
-            TilePosition::TopLeft | TilePosition::TopCenter | TilePosition::TopRight => 1.0,
-            TilePosition::CenterLeft | TilePosition::Center | TilePosition::CenterRight => 0.0,
-            TilePosition::BottomLeft | TilePosition::BottomCenter | TilePosition::BottomRight => {
-                -1.0
-            }
-            TilePosition::None => 0.0,
-        }
+impl Grid {
+    pub const DEFAULT: Grid = Grid { size: 3 };
+    /// Sizes offered by the menu.
+    pub const SIZES: [usize; 4] = [2, 3, 4, 5];
+
+    pub const fn cells(&self) -> usize {
+        self.size * self.size
     }
 
-    fn column(&self) -> f32 {
-        match self {
-            TilePosition::TopLeft | TilePosition::CenterLeft | TilePosition::BottomLeft => -1.0,
-            TilePosition::TopCenter | TilePosition::Center | TilePosition::BottomCenter => 0.0,
-            TilePosition::TopRight | TilePosition::CenterRight | TilePosition::BottomRight => 1.0,
-            TilePosition::None => 0.0,
+    /// The grid following this one in `Grid::SIZES`, wrapping around.
+    pub fn next(&self) -> Grid {
+        let i = Grid::SIZES
+            .iter()
+            .position(|size| *size == self.size)
+            .unwrap_or(0);
+        Grid {
+            size: Grid::SIZES[(i + 1) % Grid::SIZES.len()],
         }
     }
-}
 
-impl Distribution<TilePosition> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TilePosition {
-        match rng.gen_range(0..=9) {
-            0 => TilePosition::TopLeft,
-            1 => TilePosition::TopCenter,
-            2 => TilePosition::TopRight,
-            3 => TilePosition::CenterLeft,
-            4 => TilePosition::Center,
-            5 => TilePosition::CenterRight,
-            6 => TilePosition::BottomLeft,
-            7 => TilePosition::BottomCenter,
-            _ => TilePosition::BottomRight,
-        }
+    pub fn label(&self) -> String {
+        format!("{}x{}", self.size, self.size)
     }
-}
 
-impl From<&TilePosition> for Vec3 {
-    fn from(tile: &TilePosition) -> Self {
+    /// Length of the walls around the grid.
+    pub fn edge(&self) -> f32 {
+        (config::TILE_SIZE * self.size as f32) + (config::TILE_SPACING * (self.size + 1) as f32)
+    }
+
+    /// Translation of the tile on the given cell.
+    pub fn translation(&self, position: &TilePosition) -> Vec3 {
+        let center = (self.size as f32 - 1.0) / 2.0;
+        let (row, column) = match position.0 {
+            Some(cell) => ((cell / self.size) as f32, (cell % self.size) as f32),
+            None => (center, center),
+        };
+
         Vec3::new(
-            tile.column() * (config::TILE_SIZE + config::TILE_SPACING),
-            tile.row() * (config::TILE_SIZE + config::TILE_SPACING),
+            (column - center) * (config::TILE_SIZE + config::TILE_SPACING),
+            (center - row) * (config::TILE_SIZE + config::TILE_SPACING),
             0.0,
         )
     }
 }
+
+impl Default for Grid {
+    fn default() -> Self {
+        Grid::DEFAULT
+    }
+}
+
+/// Most tiles shown at once in multi-stimulus games.
+pub const MAX_TILES: usize = 4;
+
+/// Identifier of the position modality of every tile.
+pub const POSITION_IDS: [ModalityId; MAX_TILES] = [
+    ModalityId("position"),
+    ModalityId("position 2"),
+    ModalityId("position 3"),
+    ModalityId("position 4"),
+];
+
+const POSITION_LABELS: [&str; MAX_TILES] = ["Position", "Position 2", "Position 3", "Position 4"];
+
+const POSITION_ABBREVS: [&str; MAX_TILES] = ["P", "P2", "P3", "P4"];
+
+const POSITION_KEYS: [KeyCode; MAX_TILES] =
+    [KeyCode::KeyA, KeyCode::KeyJ, KeyCode::KeyK, KeyCode::KeyL];
+
+/// Position of a tile on the grid, the first tile unless `TILE` is given.
+pub struct PositionModality<const TILE: usize = 0>;
+
+impl<const TILE: usize> Modality for PositionModality<TILE> {
+    type Cue = TilePosition;
+
+    const ID: ModalityId = POSITION_IDS[TILE];
+    const LABEL: &'static str = POSITION_LABELS[TILE];
+    const ABBREV: &'static str = POSITION_ABBREVS[TILE];
+    const KEY: KeyCode = POSITION_KEYS[TILE];
+    const ENABLED: bool = TILE == 0;
+    const SYMBOLS: usize = Grid::DEFAULT.cells();
+    const TILE: usize = TILE;
+
+    fn symbols(settings: &GameSettings) -> usize {
+        settings.grid.cells()
+    }
+
+    fn cue(symbol: Symbol) -> TilePosition {
+        TilePosition(Some(symbol))
+    }
+
+    fn present(app: &mut App) {
+        // a single system moves every tile
+        if TILE == 0 {
+            app.add_systems(
+                Update,
+                tile_position_system.run_if(in_state(AppState::Game)),
+            );
+        }
+    }
+}
+
+/// Update tile state every time the position changes.
+pub fn tile_position_system(
+    settings: Res<GameSettings>,
+    mut query: Query<(&mut Transform, &mut AnimationPlayer, &TilePosition), Changed<TilePosition>>,
+) {
+    for (mut transform, mut animation, position) in &mut query {
+        info!(?position, "tile updated");
+        transform.translation = settings.grid.translation(position);
+        animation.replay();
+    }
+}
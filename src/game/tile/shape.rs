@@ -0,0 +1,203 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, TAU};
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+use nback_core::cue::Symbol;
+
+use crate::{
+    config,
+    game::modality::{Modality, ModalityId},
+    palette,
+    state::AppState,
+};
+
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub enum TileShape {
+    Circle,
+    Square,
+    Triangle,
+    Diamond,
+    Pentagon,
+    Hexagon,
+    Star,
+    Cross,
+    #[default]
+    None,
+}
+
+impl TileShape {
+    pub const ALL: [TileShape; 8] = [
+        TileShape::Circle,
+        TileShape::Square,
+        TileShape::Triangle,
+        TileShape::Diamond,
+        TileShape::Pentagon,
+        TileShape::Hexagon,
+        TileShape::Star,
+        TileShape::Cross,
+    ];
+
+    /// Counter-clockwise outline of the shape, centered on the origin.
+    fn outline(&self) -> Vec<Vec2> {
+        let radius = config::TILE_SHAPE_RADIUS;
+        match self {
+            TileShape::Circle => regular_polygon(48, radius, 0.0),
+            TileShape::Square => regular_polygon(4, radius, FRAC_PI_4),
+            TileShape::Triangle => regular_polygon(3, radius, FRAC_PI_2),
+            TileShape::Diamond => regular_polygon(4, radius, 0.0),
+            TileShape::Pentagon => regular_polygon(5, radius, FRAC_PI_2),
+            TileShape::Hexagon => regular_polygon(6, radius, 0.0),
+            TileShape::Star => regular_polygon(10, radius, FRAC_PI_2)
+                .into_iter()
+                .enumerate()
+                .map(|(i, point)| if i % 2 == 0 { point } else { point * 0.45 })
+                .collect(),
+            TileShape::Cross => {
+                let (a, b) = (radius / 3.0, radius);
+                [
+                    (a, -a),
+                    (b, -a),
+                    (b, a),
+                    (a, a),
+                    (a, b),
+                    (-a, b),
+                    (-a, a),
+                    (-b, a),
+                    (-b, -a),
+                    (-a, -a),
+                    (-a, -b),
+                    (a, -b),
+                ]
+                .into_iter()
+                .map(|(x, y)| Vec2::new(x, y))
+                .collect()
+            }
+            TileShape::None => Vec::new(),
+        }
+    }
+}
+
+fn regular_polygon(sides: usize, radius: f32, phase: f32) -> Vec<Vec2> {
+    (0..sides)
+        .map(|i| Vec2::from_angle(phase + TAU * i as f32 / sides as f32) * radius)
+        .collect()
+}
+
+impl From<&TileShape> for Mesh {
+    /// Triangle fan around the center of the shape, which suits any outline
+    /// visible in full from its center.
+    fn from(shape: &TileShape) -> Self {
+        let outline = shape.outline();
+
+        let mut positions = vec![[0.0, 0.0, 0.0]];
+        positions.extend(outline.iter().map(|point| [point.x, point.y, 0.0]));
+
+        let sides = outline.len() as u32;
+        let indices = (0..sides)
+            .flat_map(|i| [0, i + 1, (i + 1) % sides + 1])
+            .collect();
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            vec![[0.0, 0.0, 1.0]; positions.len()],
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; positions.len()])
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+/// Shape drawn on the tile.
+pub struct ShapeModality;
+
+impl Modality for ShapeModality {
+    type Cue = TileShape;
+
+    const ID: ModalityId = ModalityId("shape");
+    const LABEL: &'static str = "Shape";
+    const ABBREV: &'static str = "Sh";
+    const KEY: KeyCode = KeyCode::KeyF;
+    const ENABLED: bool = false;
+    const SYMBOLS: usize = TileShape::ALL.len();
+
+    fn cue(symbol: Symbol) -> TileShape {
+        TileShape::ALL[symbol].clone()
+    }
+
+    fn present(app: &mut App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, tile_shape_system.run_if(in_state(AppState::Game)));
+    }
+}
+
+/// Meshes of every shape, in the order of `TileShape::ALL`, and their material.
+#[derive(Resource)]
+pub struct ShapeAssets {
+    meshes: Vec<Handle<Mesh>>,
+    material: Handle<ColorMaterial>,
+}
+
+/// Tag component of the child of the tile drawing its shape.
+#[derive(Component)]
+pub struct ShapeMesh;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.insert_resource(ShapeAssets {
+        meshes: TileShape::ALL
+            .iter()
+            .map(|shape| meshes.add(Mesh::from(shape)))
+            .collect(),
+        material: materials.add(ColorMaterial::from(palette::SLATE_900)),
+    });
+}
+
+/// Update tile state every time the shape changes.
+pub fn tile_shape_system(
+    mut commands: Commands,
+    assets: Res<ShapeAssets>,
+    query: Query<(Entity, &TileShape, Option<&Children>), Changed<TileShape>>,
+    mut shape_meshes: Query<&mut Mesh2dHandle, With<ShapeMesh>>,
+) {
+    for (tile, shape, children) in &query {
+        info!(?shape, "tile updated");
+        let Some(i) = TileShape::ALL.iter().position(|other| other == shape) else {
+            continue;
+        };
+        let mesh = Mesh2dHandle(assets.meshes[i].clone());
+
+        let child = children
+            .into_iter()
+            .flatten()
+            .find(|child| shape_meshes.contains(**child));
+        match child {
+            Some(child) => *shape_meshes.get_mut(*child).unwrap() = mesh,
+            None => {
+                commands.entity(tile).with_children(|parent| {
+                    parent.spawn((
+                        MaterialMesh2dBundle {
+                            mesh,
+                            material: assets.material.clone(),
+                            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                            ..default()
+                        },
+                        ShapeMesh,
+                    ));
+                });
+            }
+        }
+    }
+}
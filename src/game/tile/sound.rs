@@ -1,10 +1,18 @@
 use bevy::prelude::*;
-use rand::{
-    distributions::{Distribution, Standard},
-    Rng,
+use bevy_kira_audio::{prelude::*, AudioSource};
+use nback_core::cue::Symbol;
+
+use crate::{
+    asset::AudioAssets,
+    config,
+    game::{
+        modality::{Modality, ModalityId},
+        settings::GameSettings,
+    },
+    state::AppState,
 };
 
-use crate::config;
+use super::pan::TilePan;
 
 #[derive(Component, Clone, Debug, Default, PartialEq)]
 pub enum TileSound {
@@ -20,17 +28,46 @@ pub enum TileSound {
     None,
 }
 
-impl Distribution<TileSound> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TileSound {
-        match rng.gen_range(0..=5) {
-            0 => TileSound::C,
-            1 => TileSound::H,
-            2 => TileSound::K,
-            3 => TileSound::L,
-            4 => TileSound::Q,
-            5 => TileSound::R,
-            6 => TileSound::S,
-            _ => TileSound::T,
+impl TileSound {
+    pub const ALL: [TileSound; 8] = [
+        TileSound::C,
+        TileSound::H,
+        TileSound::K,
+        TileSound::L,
+        TileSound::Q,
+        TileSound::R,
+        TileSound::S,
+        TileSound::T,
+    ];
+}
+
+impl TileSound {
+    pub fn letter(&self) -> Option<char> {
+        match self {
+            TileSound::C => Some('C'),
+            TileSound::H => Some('H'),
+            TileSound::K => Some('K'),
+            TileSound::L => Some('L'),
+            TileSound::Q => Some('Q'),
+            TileSound::R => Some('R'),
+            TileSound::S => Some('S'),
+            TileSound::T => Some('T'),
+            TileSound::None => None,
+        }
+    }
+
+    /// Recording of the letter, if any.
+    pub fn source(&self, assets: &AudioAssets) -> Option<Handle<AudioSource>> {
+        match self {
+            TileSound::C => Some(assets.c.clone()),
+            TileSound::H => Some(assets.h.clone()),
+            TileSound::K => Some(assets.k.clone()),
+            TileSound::L => Some(assets.l.clone()),
+            TileSound::Q => Some(assets.q.clone()),
+            TileSound::R => Some(assets.r.clone()),
+            TileSound::S => Some(assets.s.clone()),
+            TileSound::T => Some(assets.t.clone()),
+            TileSound::None => None,
         }
     }
 }
@@ -50,3 +87,47 @@ impl From<&TileSound> for Option<&str> {
         }
     }
 }
+
+/// The letter read aloud.
+pub struct SoundModality;
+
+impl Modality for SoundModality {
+    type Cue = TileSound;
+
+    const ID: ModalityId = ModalityId("sound");
+    const LABEL: &'static str = "Sound";
+    const ABBREV: &'static str = "S";
+    const KEY: KeyCode = KeyCode::KeyS;
+    const SYMBOLS: usize = TileSound::ALL.len();
+
+    fn cue(symbol: Symbol) -> TileSound {
+        TileSound::ALL[symbol].clone()
+    }
+
+    fn present(app: &mut App) {
+        app.add_systems(
+            Update,
+            // NOTE the run_if guard is a woraround for running the tile_sound_system
+            // only when the audio resource handles are ready
+            tile_sound_system.run_if(in_state(AppState::Game)),
+        );
+    }
+}
+
+/// Play the tile sound every time it changes, panned to the stereo position
+/// of the tile if it has one.
+pub fn tile_sound_system(
+    audio: Res<Audio>,
+    audio_assets: Res<AudioAssets>,
+    settings: Res<GameSettings>,
+    query: Query<(&TileSound, Option<&TilePan>), Changed<TileSound>>,
+) {
+    for (sound, pan) in &query {
+        if let Some(source) = sound.source(&audio_assets) {
+            let mut play = audio.play(source);
+            if let Some(panning) = pan.and_then(|pan| settings.stereo.panning(pan)) {
+                play.with_panning(panning);
+            }
+        }
+    }
+}
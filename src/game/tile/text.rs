@@ -0,0 +1,62 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use bevy::prelude::*;
+
+use crate::palette;
+
+/// Cue written on the tile, such as a letter or a number.
+pub trait TileText: Component + Debug {
+    /// Text written for the cue, empty when there is none.
+    fn text(&self) -> String;
+}
+
+/// Tag component of the child of the tile writing its `T`.
+#[derive(Component)]
+pub struct TileTextChild<T>(PhantomData<T>);
+
+impl<T> Default for TileTextChild<T> {
+    fn default() -> Self {
+        TileTextChild(PhantomData)
+    }
+}
+
+/// Update tile state every time the cue written on it changes, writing it
+/// on a child of the tile spawned the first time.
+pub fn tile_text_system<T: TileText>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    query: Query<(Entity, &T, Option<&Children>), Changed<T>>,
+    mut texts: Query<&mut Text, With<TileTextChild<T>>>,
+) {
+    for (tile, cue, children) in &query {
+        info!(?cue, "tile updated");
+        let value = cue.text();
+
+        let child = children
+            .into_iter()
+            .flatten()
+            .find(|child| texts.contains(**child));
+        match child {
+            Some(child) => texts.get_mut(*child).unwrap().sections[0].value = value,
+            None => {
+                commands.entity(tile).with_children(|parent| {
+                    parent.spawn((
+                        Text2dBundle {
+                            text: Text::from_section(
+                                value,
+                                TextStyle {
+                                    font: asset_server.load("embedded://fonts/FiraSans-Bold.ttf"),
+                                    font_size: 60.0,
+                                    color: palette::SLATE_900,
+                                },
+                            ),
+                            transform: Transform::from_xyz(0.0, 0.0, 2.0),
+                            ..default()
+                        },
+                        TileTextChild::<T>::default(),
+                    ));
+                });
+            }
+        }
+    }
+}
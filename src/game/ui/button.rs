@@ -1,6 +1,15 @@
 use bevy::prelude::*;
 
-use crate::{game::core::round::Answer, palette, state::AppState};
+use crate::{
+    game::{
+        core::{cue::CueTimer, state::GameState, GameSession},
+        modality::ModalityId,
+        pause::PauseEvent,
+        TrialSet,
+    },
+    palette,
+    state::AppState,
+};
 
 pub const NORMAL_BUTTON: Color = palette::SLATE_800;
 pub const HOVERED_BUTTON: Color = palette::TEAL_600;
@@ -13,9 +22,10 @@ pub struct Shortcut(pub KeyCode);
 
 #[derive(Component)]
 pub enum ButtonAction {
-    SamePosition,
-    SameSound,
-    SameColor,
+    /// Answer a match of the modality.
+    Match(ModalityId),
+    /// Pause, resume, restart or quit the game.
+    Pause(PauseEvent),
 }
 
 #[derive(Bundle)]
@@ -31,14 +41,19 @@ impl Plugin for GameButtonPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (button_system, button_shortcut_system).run_if(in_state(AppState::Game)),
+            (
+                button_system.in_set(TrialSet::Answer),
+                button_shortcut_system,
+            )
+                .run_if(in_state(AppState::Game)),
         );
     }
 }
 
 #[allow(clippy::type_complexity)]
 fn button_system(
-    mut answer: ResMut<Answer>,
+    mut sessions: Query<(&mut GameSession, &GameState, &CueTimer)>,
+    mut pause_events: EventWriter<PauseEvent>,
     mut query: Query<
         (
             &Interaction,
@@ -55,9 +70,16 @@ fn button_system(
                 *color = PRESSED_BUTTON.into();
                 border_color.0 = BUTTON_BORDER_COLOR;
                 match action {
-                    ButtonAction::SamePosition => answer.position = true,
-                    ButtonAction::SameSound => answer.sound = true,
-                    ButtonAction::SameColor => answer.color = true,
+                    ButtonAction::Match(id) => {
+                        if let Ok((mut session, state, timer)) = sessions.get_single_mut() {
+                            if *state == GameState::Playing {
+                                session.answer_match(*id, timer.elapsed_secs());
+                            }
+                        }
+                    }
+                    ButtonAction::Pause(event) => {
+                        pause_events.send(*event);
+                    }
                 }
             }
             Interaction::Hovered => {
@@ -1,13 +1,23 @@
 use bevy::prelude::*;
 
-use crate::state::{AppState, OnGameScreen};
+use crate::{
+    palette,
+    state::{AppState, OnGameScreen},
+};
 
 use self::{
-    button::{GameButtonBundle, Shortcut},
-    text::{round_system, CurrentRoundText},
+    button::{ButtonAction, GameButtonBundle, Shortcut},
+    text::{
+        current_n_system, round_system, typed_answer_system, CurrentNText, CurrentRoundText,
+        TypedAnswerText,
+    },
 };
 
-use super::settings::GameSettings;
+use super::{
+    modality::{Modalities, ModalityInfo},
+    pause::PauseEvent,
+    settings::{GameMode, GameSettings},
+};
 
 pub mod button;
 pub mod text;
@@ -17,13 +27,17 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(AppState::Game), game_ui)
-            .add_systems(Update, round_system);
+            .add_systems(
+                Update,
+                (round_system, typed_answer_system, current_n_system),
+            );
     }
 }
 
 pub fn game_ui(
     mut commands: Commands,
     settings: Res<GameSettings>,
+    modalities: Res<Modalities>,
     asset_server: Res<AssetServer>,
 ) {
     let font = asset_server.load("embedded://fonts/FiraSans-Bold.ttf");
@@ -57,7 +71,7 @@ pub fn game_ui(
                     },
                     OnGameScreen,
                 ))
-                .with_children(|parent| game_info(parent, settings, font.clone()));
+                .with_children(|parent| game_info(parent, &settings, &modalities, font.clone()));
 
             parent
                 .spawn((
@@ -65,99 +79,149 @@ pub fn game_ui(
                         style: Style {
                             flex_grow: 1.0,
                             flex_direction: FlexDirection::Row,
+                            flex_wrap: FlexWrap::Wrap,
                             align_items: AlignItems::End,
+                            align_content: AlignContent::End,
                             justify_content: JustifyContent::SpaceBetween,
+                            row_gap: Val::Px(10.0),
                             ..default()
                         },
                         ..default()
                     },
                     OnGameScreen,
                 ))
-                .with_children(|parent| buttons(parent, font));
+                .with_children(|parent| match settings.mode {
+                    GameMode::NBack | GameMode::Combination => {
+                        let crosses = settings.crosses(&modalities);
+                        for modality in settings
+                            .enabled_cues()
+                            .map(|(id, _)| id)
+                            .chain(crosses.iter().map(|(id, _)| *id))
+                            .filter_map(|id| modalities.get(id))
+                        {
+                            button(parent, modality, font.clone());
+                        }
+                    }
+                    GameMode::Arithmetic => {
+                        parent.spawn((
+                            TextBundle::from_section(
+                                "= ",
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 40.0,
+                                    color: Color::rgb(0.9, 0.9, 0.9),
+                                },
+                            ),
+                            TypedAnswerText,
+                        ));
+                    }
+                });
         });
 }
 
-fn game_info(parent: &mut ChildBuilder, settings: Res<GameSettings>, font: Handle<Font>) {
-    parent.spawn(TextBundle::from_section(
-        format!("{}-Back", settings.n),
-        TextStyle {
-            font: font.clone(),
-            font_size: 40.0,
-            color: Color::rgb(0.9, 0.9, 0.9),
-        },
-    ));
-    parent.spawn((
-        TextBundle::from_section(
-            "",
-            TextStyle {
-                font: font.clone(),
-                font_size: 40.0,
-                color: Color::rgb(0.9, 0.9, 0.9),
-            },
-        ),
-        CurrentRoundText,
-    ));
-}
-
-fn buttons(parent: &mut ChildBuilder, font: Handle<Font>) {
+fn game_info(
+    parent: &mut ChildBuilder,
+    settings: &GameSettings,
+    modalities: &Modalities,
+    font: Handle<Font>,
+) {
     parent
-        .spawn(GameButtonBundle {
-            button: ButtonBundle {
-                style: Style {
-                    width: Val::Px(150.0),
-                    height: Val::Px(65.0),
-                    border: UiRect::all(Val::Px(3.0)),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                border_color: button::BUTTON_BORDER_COLOR.into(),
-                background_color: button::NORMAL_BUTTON.into(),
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
                 ..default()
             },
-            shortcut: Shortcut(KeyCode::KeyA),
-            action: button::ButtonAction::SamePosition,
+            ..default()
         })
         .with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                "Position (A)",
-                TextStyle {
-                    font: font.clone(),
-                    font_size: 20.0,
-                    color: Color::rgb(0.9, 0.9, 0.9),
-                },
-            ));
+            for (id, cue) in settings.enabled_cues() {
+                parent.spawn(TextBundle::from_section(
+                    format!("{} {}-Back", modalities.label(id), cue.n),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 32.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ));
+            }
+            if settings.variable_n {
+                parent.spawn((
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 64.0,
+                            color: palette::AMBER_400,
+                        },
+                    ),
+                    CurrentNText,
+                ));
+            }
+            if settings.mode == GameMode::Arithmetic {
+                parent.spawn(TextBundle::from_section(
+                    format!(
+                        "{} ({})",
+                        settings.operation.label(),
+                        settings.operation.sign()
+                    ),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 32.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ));
+            }
         });
-
     parent
-        .spawn(GameButtonBundle {
-            button: ButtonBundle {
-                style: Style {
-                    width: Val::Px(150.0),
-                    height: Val::Px(65.0),
-                    border: UiRect::all(Val::Px(3.0)),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                border_color: button::BUTTON_BORDER_COLOR.into(),
-                background_color: button::NORMAL_BUTTON.into(),
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::End,
+                row_gap: Val::Px(10.0),
                 ..default()
             },
-            shortcut: Shortcut(KeyCode::KeyS),
-            action: button::ButtonAction::SameSound,
+            ..default()
         })
         .with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                "Sound (S)",
-                TextStyle {
-                    font: font.clone(),
-                    font_size: 20.0,
-                    color: Color::rgb(0.9, 0.9, 0.9),
-                },
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 40.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                CurrentRoundText,
             ));
+            action_button(
+                parent,
+                "Pause (Esc)".to_string(),
+                KeyCode::Escape,
+                ButtonAction::Pause(PauseEvent::Pause),
+                font,
+            );
         });
+}
 
+fn button(parent: &mut ChildBuilder, modality: &ModalityInfo, font: Handle<Font>) {
+    action_button(
+        parent,
+        format!("{} ({})", modality.label, modality.key_label()),
+        modality.key,
+        ButtonAction::Match(modality.id),
+        font,
+    );
+}
+
+/// A button of the game screen, pressed on screen or with its `key`.
+pub fn action_button(
+    parent: &mut ChildBuilder,
+    label: String,
+    key: KeyCode,
+    action: ButtonAction,
+    font: Handle<Font>,
+) {
     parent
         .spawn(GameButtonBundle {
             button: ButtonBundle {
@@ -173,14 +237,14 @@ fn buttons(parent: &mut ChildBuilder, font: Handle<Font>) {
                 background_color: button::NORMAL_BUTTON.into(),
                 ..default()
             },
-            shortcut: Shortcut(KeyCode::KeyD),
-            action: button::ButtonAction::SameColor,
+            shortcut: Shortcut(key),
+            action,
         })
         .with_children(|parent| {
             parent.spawn(TextBundle::from_section(
-                "Color (D)",
+                label,
                 TextStyle {
-                    font: font.clone(),
+                    font,
                     font_size: 20.0,
                     color: Color::rgb(0.9, 0.9, 0.9),
                 },
@@ -1,19 +1,51 @@
 use bevy::prelude::*;
 
-use crate::game::{settings::GameSettings, EndOfRoundEvent};
+use crate::game::{core::GameSession, input::TypedAnswer, StartOfRoundEvent};
 
 #[derive(Component)]
 pub struct CurrentRoundText;
 
 #[allow(clippy::type_complexity)]
 pub fn round_system(
-    settings: Res<GameSettings>,
-    mut events: EventReader<EndOfRoundEvent>,
+    mut events: EventReader<StartOfRoundEvent>,
+    sessions: Query<&GameSession>,
     mut query: Query<(&mut Text, &CurrentRoundText)>,
 ) {
-    for (text, _) in &mut query.get_single_mut() {
+    if let (Ok((mut text, _)), Ok(session)) = (query.get_single_mut(), sessions.get_single()) {
         for e in events.read() {
-            text.sections[0].value = format!("{}/{}", e.round, settings.rounds);
+            text.sections[0].value = format!("{}/{}", e.0 + 1, session.round().total);
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct TypedAnswerText;
+
+pub fn typed_answer_system(
+    typed: Res<TypedAnswer>,
+    mut query: Query<&mut Text, With<TypedAnswerText>>,
+) {
+    if typed.is_changed() {
+        if let Ok(mut text) = query.get_single_mut() {
+            text.sections[0].value = format!("= {}", typed.as_str());
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct CurrentNText;
+
+/// Show the N of the latest cue of variable-N games.
+pub fn current_n_system(
+    mut events: EventReader<StartOfRoundEvent>,
+    sessions: Query<&GameSession>,
+    mut query: Query<&mut Text, With<CurrentNText>>,
+) {
+    for _ in events.read() {
+        if let (Ok(session), Ok(mut text)) = (sessions.get_single(), query.get_single_mut()) {
+            if let Some(n) = session.engine().current_n() {
+                text.sections[0].value = format!("{}-Back", n);
+            }
         }
     }
 }
@@ -1,6 +1,17 @@
 use bevy::prelude::*;
+use nback_core::{alphabet::Alphabet, plan::SessionPlan};
 
-use crate::{game::settings::GameSettings, palette, state::AppState};
+use crate::{
+    config,
+    game::{
+        input::digit,
+        modality::{Modalities, ModalityId},
+        settings::GameSettings,
+        tile::position::MAX_TILES,
+    },
+    palette,
+    state::AppState,
+};
 
 pub const NORMAL_BUTTON: Color = palette::SLATE_800;
 pub const HOVERED_BUTTON: Color = palette::LIME_900;
@@ -36,22 +47,140 @@ pub fn play_button_system(
 }
 
 #[derive(Component)]
-pub struct IncreaseNButton;
+pub struct IncreaseNButton(pub ModalityId);
 
 #[allow(clippy::type_complexity)]
 pub fn increase_n_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<(&Interaction, &mut BackgroundColor, &IncreaseNButton), Changed<Interaction>>,
+) {
+    for (interaction, mut color, button) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                if let Some(cue) = settings.cue_mut(button.0) {
+                    cue.n += 1;
+                }
+                settings.set_rounds_from_n();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct DecreaseNButton(pub ModalityId);
+
+#[allow(clippy::type_complexity)]
+pub fn decrease_n_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<(&Interaction, &mut BackgroundColor, &DecreaseNButton), Changed<Interaction>>,
+) {
+    for (interaction, mut color, button) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                if let Some(cue) = settings.cue_mut(button.0) {
+                    cue.n = cue.n.saturating_sub(1).max(1);
+                }
+                settings.set_rounds_from_n();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Button cycling the number of symbols a modality draws its cues from, from
+/// 2 up to all of them.
+#[derive(Component)]
+pub struct AlphabetButton(pub ModalityId);
+
+#[allow(clippy::type_complexity)]
+pub fn alphabet_button_system(
+    mut settings: ResMut<GameSettings>,
+    modalities: Res<Modalities>,
+    mut query: Query<(&Interaction, &mut BackgroundColor, &AlphabetButton), Changed<Interaction>>,
+) {
+    for (interaction, mut color, button) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                let Some(symbols) = modalities.get(button.0).map(|m| m.symbols(&settings)) else {
+                    continue;
+                };
+                if let Some(cue) = settings.cue_mut(button.0) {
+                    let size = cue.active_symbols(symbols).len();
+                    cue.alphabet = if size >= symbols {
+                        Some(Alphabet::first(2))
+                    } else if size + 1 < symbols {
+                        Some(Alphabet::first(size + 1))
+                    } else {
+                        None
+                    };
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Button cycling the lure rate of a modality.
+#[derive(Component)]
+pub struct LureButton(pub ModalityId);
+
+#[allow(clippy::type_complexity)]
+pub fn lure_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<(&Interaction, &mut BackgroundColor, &LureButton), Changed<Interaction>>,
+) {
+    for (interaction, mut color, button) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.cycle_lure_rate(button.0);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Button cycling the operation of arithmetic games.
+#[derive(Component)]
+pub struct OperationButton;
+
+#[allow(clippy::type_complexity)]
+pub fn operation_button_system(
     mut settings: ResMut<GameSettings>,
     mut query: Query<
         (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<IncreaseNButton>),
+        (Changed<Interaction>, With<OperationButton>),
     >,
 ) {
     for (interaction, mut color) in &mut query {
         match *interaction {
             Interaction::Pressed => {
                 *color = PRESSED_BUTTON.into();
-                settings.n += 1;
-                settings.set_rounds_from_n();
+                settings.operation = settings.operation.next();
             }
             Interaction::Hovered => {
                 *color = HOVERED_BUTTON.into();
@@ -63,22 +192,306 @@ pub fn increase_n_button_system(
     }
 }
 
+/// Button cycling the grid of the position modality.
 #[derive(Component)]
-pub struct DecreaseNButton;
+pub struct GridButton;
 
 #[allow(clippy::type_complexity)]
-pub fn decrease_n_button_system(
+pub fn grid_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<GridButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.grid = settings.grid.next();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Button cycling the scale of the pitch modality.
+#[derive(Component)]
+pub struct ScaleButton;
+
+#[allow(clippy::type_complexity)]
+pub fn scale_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ScaleButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.scale = settings.scale.next();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Button cycling how long the cues are shown.
+#[derive(Component)]
+pub struct StimulusButton;
+
+#[allow(clippy::type_complexity)]
+pub fn stimulus_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<StimulusButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.cycle_stimulus_time();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Button cycling how long the tile stays hidden between the cues.
+#[derive(Component)]
+pub struct IntervalButton;
+
+#[allow(clippy::type_complexity)]
+pub fn interval_button_system(
     mut settings: ResMut<GameSettings>,
     mut query: Query<
         (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<DecreaseNButton>),
+        (Changed<Interaction>, With<IntervalButton>),
     >,
 ) {
     for (interaction, mut color) in &mut query {
         match *interaction {
             Interaction::Pressed => {
                 *color = PRESSED_BUTTON.into();
-                settings.n -= 1;
+                settings.cycle_interval_time();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Button cycling how the interval varies between trials.
+#[derive(Component)]
+pub struct JitterButton;
+
+#[allow(clippy::type_complexity)]
+pub fn jitter_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<JitterButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.jitter = settings.jitter.next();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Button cycling the progression of the N between games.
+#[derive(Component)]
+pub struct ProgressionButton;
+
+#[allow(clippy::type_complexity)]
+pub fn progression_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ProgressionButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.cycle_progression();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Seed being typed in the menu, `None` when the player is not typing one.
+#[derive(Resource, Debug, Default)]
+pub struct SeedEntry(pub Option<String>);
+
+impl SeedEntry {
+    /// Seed the cues of the next game are drawn from, if any was typed.
+    fn finish(&mut self, settings: &mut GameSettings) {
+        if let Some(seed) = self.0.take().and_then(|digits| digits.parse().ok()) {
+            settings.seed = seed;
+        }
+    }
+}
+
+/// Button typing the seed of the next game, or setting the one typed.
+#[derive(Component)]
+pub struct SeedButton;
+
+#[allow(clippy::type_complexity)]
+pub fn seed_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut entry: ResMut<SeedEntry>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<SeedButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                if entry.0.is_some() {
+                    entry.finish(&mut settings);
+                } else {
+                    entry.0 = Some(String::new());
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Type the digits of the seed, set it with Enter or leave it with Escape.
+pub fn seed_key_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<GameSettings>,
+    mut entry: ResMut<SeedEntry>,
+) {
+    let Some(digits) = entry.0.as_mut() else {
+        return;
+    };
+
+    for key in keyboard_input.get_just_pressed() {
+        match key {
+            KeyCode::Backspace => {
+                digits.pop();
+            }
+            key => {
+                if let Some(digit) = digit(key) {
+                    digits.extend(char::from_digit(digit, 10));
+                    // keep the seed within a u64
+                    if digits.parse::<u64>().is_err() {
+                        digits.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Enter)
+        || keyboard_input.just_pressed(KeyCode::NumpadEnter)
+    {
+        entry.finish(&mut settings);
+    } else if keyboard_input.just_pressed(KeyCode::Escape) {
+        entry.0 = None;
+    }
+}
+
+/// Leave the seed typed in the menu unfinished once the menu is left.
+pub fn reset_seed_entry(mut entry: ResMut<SeedEntry>) {
+    entry.0 = None;
+}
+
+/// Button cycling the stereo positions of the sound position modality.
+#[derive(Component)]
+pub struct StereoButton;
+
+#[allow(clippy::type_complexity)]
+pub fn stereo_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<StereoButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.stereo = settings.stereo.next();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Button cycling the number of tiles shown at once.
+#[derive(Component)]
+pub struct TilesButton;
+
+#[allow(clippy::type_complexity)]
+pub fn tiles_button_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<TilesButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.tiles = settings.tiles % MAX_TILES + 1;
                 settings.set_rounds_from_n();
             }
             Interaction::Hovered => {
@@ -90,3 +503,44 @@ pub fn decrease_n_button_system(
         }
     }
 }
+
+/// Button saving the plan of a game played with the current settings.
+#[derive(Component)]
+pub struct SavePlanButton;
+
+#[allow(clippy::type_complexity)]
+pub fn save_plan_button_system(
+    settings: Res<GameSettings>,
+    modalities: Res<Modalities>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<SavePlanButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                let plan = SessionPlan {
+                    stimulus_time: Some(settings.stimulus_time),
+                    ..SessionPlan::record(
+                        settings.engine(&modalities),
+                        settings.rounds,
+                        settings.round_time(),
+                        settings.task(),
+                    )
+                };
+                match plan.save(config::PLAN_FILE) {
+                    Ok(()) => info!(file = config::PLAN_FILE, "plan saved"),
+                    Err(err) => error!(%err, "cannot save the plan"),
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
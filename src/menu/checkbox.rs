@@ -1,6 +1,14 @@
 use bevy::prelude::*;
+use nback_core::{block::MatchQuota, cue::TargetOffset, plan::SessionPlan};
 
-use crate::{game::settings::GameSettings, palette};
+use crate::{
+    config,
+    game::{
+        modality::ModalityId,
+        settings::{GameMode, GameSettings},
+    },
+    palette,
+};
 
 pub const NORMAL_BUTTON: Color = palette::SLATE_800;
 pub const HOVERED_BUTTON: Color = palette::LIME_900;
@@ -13,15 +21,54 @@ pub struct Checkbox {
     pub checked: bool,
 }
 
+/// Checkbox enabling a modality.
 #[derive(Component)]
-pub struct PositionCheckBox;
+pub struct ModalityCheckbox(pub ModalityId);
 
 #[allow(clippy::type_complexity)]
-pub fn position_checkbox_system(
+pub fn modality_checkbox_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            &mut Checkbox,
+            &ModalityCheckbox,
+        ),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, mut color, mut checkbox, modality) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                if checkbox.checked {
+                    *color = NORMAL_BUTTON.into();
+                    checkbox.checked = false;
+                } else {
+                    *color = PRESSED_BUTTON.into();
+                    checkbox.checked = true;
+                }
+
+                if let Some(cue) = settings.cue_mut(modality.0) {
+                    cue.enabled = checkbox.checked;
+                }
+                settings.set_rounds_from_n();
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {}
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct QuotaCheckbox;
+
+#[allow(clippy::type_complexity)]
+pub fn quota_checkbox_system(
     mut settings: ResMut<GameSettings>,
     mut query: Query<
         (&Interaction, &mut BackgroundColor, &mut Checkbox),
-        (Changed<Interaction>, With<PositionCheckBox>),
+        (Changed<Interaction>, With<QuotaCheckbox>),
     >,
 ) {
     for (interaction, mut color, mut checkbox) in &mut query {
@@ -35,7 +82,7 @@ pub fn position_checkbox_system(
                     checkbox.checked = true;
                 }
 
-                settings.position = checkbox.checked;
+                settings.quota = checkbox.checked.then_some(MatchQuota::JAEGGI);
             }
             Interaction::Hovered => {}
             Interaction::None => {}
@@ -43,15 +90,50 @@ pub fn position_checkbox_system(
     }
 }
 
+/// Checkbox playing the games of a mode other than plain n-back, see
+/// [`GameMode`]. Only one mode is checked at a time.
 #[derive(Component)]
-pub struct SoundCheckbox;
+pub struct ModeCheckbox(pub GameMode);
 
 #[allow(clippy::type_complexity)]
-pub fn sound_checkbox_system(
+pub fn mode_checkbox_system(
+    mut settings: ResMut<GameSettings>,
+    interactions: Query<(&Interaction, &ModeCheckbox), Changed<Interaction>>,
+    mut query: Query<(&ModeCheckbox, &mut BackgroundColor, &mut Checkbox)>,
+) {
+    for (interaction, mode) in &interactions {
+        if *interaction == Interaction::Pressed {
+            settings.mode = if settings.mode == mode.0 {
+                GameMode::NBack
+            } else {
+                mode.0
+            };
+            settings.set_rounds_from_n();
+        }
+    }
+
+    // the mode may also change with the plan loaded
+    if settings.is_changed() {
+        for (mode, mut color, mut checkbox) in &mut query {
+            checkbox.checked = settings.mode == mode.0;
+            *color = if checkbox.checked {
+                PRESSED_BUTTON.into()
+            } else {
+                NORMAL_BUTTON.into()
+            };
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct VariableNCheckbox;
+
+#[allow(clippy::type_complexity)]
+pub fn variable_n_checkbox_system(
     mut settings: ResMut<GameSettings>,
     mut query: Query<
         (&Interaction, &mut BackgroundColor, &mut Checkbox),
-        (Changed<Interaction>, With<SoundCheckbox>),
+        (Changed<Interaction>, With<VariableNCheckbox>),
     >,
 ) {
     for (interaction, mut color, mut checkbox) in &mut query {
@@ -65,7 +147,7 @@ pub fn sound_checkbox_system(
                     checkbox.checked = true;
                 }
 
-                settings.sound = checkbox.checked;
+                settings.variable_n = checkbox.checked;
             }
             Interaction::Hovered => {}
             Interaction::None => {}
@@ -74,14 +156,14 @@ pub fn sound_checkbox_system(
 }
 
 #[derive(Component)]
-pub struct ColorCheckBox;
+pub struct CrabCheckbox;
 
 #[allow(clippy::type_complexity)]
-pub fn color_checkbox_system(
+pub fn crab_checkbox_system(
     mut settings: ResMut<GameSettings>,
     mut query: Query<
         (&Interaction, &mut BackgroundColor, &mut Checkbox),
-        (Changed<Interaction>, With<ColorCheckBox>),
+        (Changed<Interaction>, With<CrabCheckbox>),
     >,
 ) {
     for (interaction, mut color, mut checkbox) in &mut query {
@@ -95,7 +177,48 @@ pub fn color_checkbox_system(
                     checkbox.checked = true;
                 }
 
-                settings.color = checkbox.checked;
+                settings.target = if checkbox.checked {
+                    TargetOffset::Crab
+                } else {
+                    TargetOffset::Fixed
+                };
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Checkbox playing the saved plan instead of drawing cues.
+#[derive(Component)]
+pub struct PlanCheckbox;
+
+#[allow(clippy::type_complexity)]
+pub fn plan_checkbox_system(
+    mut settings: ResMut<GameSettings>,
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor, &mut Checkbox),
+        (Changed<Interaction>, With<PlanCheckbox>),
+    >,
+) {
+    for (interaction, mut color, mut checkbox) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                if checkbox.checked {
+                    settings.plan = None;
+                } else {
+                    match SessionPlan::load(config::PLAN_FILE) {
+                        Ok(plan) => settings.apply_plan(plan),
+                        Err(err) => error!(%err, "cannot load the plan"),
+                    }
+                }
+
+                checkbox.checked = settings.plan.is_some();
+                *color = if checkbox.checked {
+                    PRESSED_BUTTON.into()
+                } else {
+                    NORMAL_BUTTON.into()
+                };
             }
             Interaction::Hovered => {}
             Interaction::None => {}
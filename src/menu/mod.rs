@@ -6,9 +6,23 @@ use crate::{
 };
 
 use self::{
-    button::{decrease_n_button_system, increase_n_button_system, play_button_system},
-    checkbox::{color_checkbox_system, position_checkbox_system, sound_checkbox_system},
-    text::nback_text_system,
+    button::{
+        alphabet_button_system, decrease_n_button_system, grid_button_system,
+        increase_n_button_system, interval_button_system, jitter_button_system, lure_button_system,
+        operation_button_system, play_button_system, progression_button_system, reset_seed_entry,
+        save_plan_button_system, scale_button_system, seed_button_system, seed_key_system,
+        stereo_button_system, stimulus_button_system, tiles_button_system, SeedEntry,
+    },
+    checkbox::{
+        crab_checkbox_system, modality_checkbox_system, mode_checkbox_system, plan_checkbox_system,
+        quota_checkbox_system, variable_n_checkbox_system,
+    },
+    text::{
+        alphabet_text_system, grid_text_system, interval_text_system, jitter_text_system,
+        lure_text_system, nback_text_system, operation_text_system, progression_text_system,
+        scale_text_system, seed_text_system, stereo_text_system, stimulus_text_system,
+        tiles_text_system,
+    },
     ui::UiPlugin,
 };
 
@@ -23,21 +37,58 @@ impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(UiPlugin)
             .add_systems(OnEnter(AppState::Menu), setup)
-            .add_systems(OnExit(AppState::Menu), despawn_screen::<OnMenuScreen>)
+            .add_systems(
+                OnExit(AppState::Menu),
+                (despawn_screen::<OnMenuScreen>, reset_seed_entry),
+            )
             .add_systems(
                 Update,
                 (
-                    nback_text_system,
-                    increase_n_button_system,
-                    decrease_n_button_system,
-                    position_checkbox_system,
-                    sound_checkbox_system,
-                    color_checkbox_system,
-                    play_button_system,
+                    (
+                        nback_text_system,
+                        increase_n_button_system,
+                        decrease_n_button_system,
+                        alphabet_button_system,
+                        alphabet_text_system,
+                        lure_button_system,
+                        lure_text_system,
+                    ),
+                    (
+                        modality_checkbox_system,
+                        quota_checkbox_system,
+                        variable_n_checkbox_system,
+                        crab_checkbox_system,
+                        mode_checkbox_system,
+                        plan_checkbox_system,
+                    ),
+                    (
+                        operation_button_system,
+                        operation_text_system,
+                        grid_button_system,
+                        grid_text_system,
+                        scale_button_system,
+                        scale_text_system,
+                        stereo_button_system,
+                        stereo_text_system,
+                        stimulus_button_system,
+                        stimulus_text_system,
+                        interval_button_system,
+                        interval_text_system,
+                        jitter_button_system,
+                        jitter_text_system,
+                        progression_button_system,
+                        progression_text_system,
+                        tiles_button_system,
+                        tiles_text_system,
+                        save_plan_button_system,
+                        play_button_system,
+                    ),
+                    (seed_button_system, seed_key_system, seed_text_system),
                 )
                     .run_if(in_state(AppState::Menu)),
             )
-            .insert_resource(GameSettings::default())
+            .init_resource::<GameSettings>()
+            .init_resource::<SeedEntry>()
             .insert_resource(LatestGameScores::default());
     }
 }
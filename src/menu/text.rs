@@ -1,15 +1,171 @@
 use bevy::prelude::*;
 
-use crate::game::settings::GameSettings;
+use crate::game::{
+    modality::{Modalities, ModalityId},
+    settings::{lure_label, time_label, GameSettings},
+};
+
+use super::button::SeedEntry;
+
+#[derive(Component)]
+pub struct NBackText(pub ModalityId);
+
+pub fn nback_text_system(settings: Res<GameSettings>, mut query: Query<(&mut Text, &NBackText)>) {
+    for (mut text, nback) in &mut query {
+        if let Some(cue) = settings.cue(nback.0) {
+            text.sections[0].value = format!("{}-Back", cue.n);
+        }
+    }
+}
+
+/// Number of symbols a modality draws its cues from.
+#[derive(Component)]
+pub struct AlphabetText(pub ModalityId);
+
+pub fn alphabet_text_system(
+    settings: Res<GameSettings>,
+    modalities: Res<Modalities>,
+    mut query: Query<(&mut Text, &AlphabetText)>,
+) {
+    for (mut text, alphabet) in &mut query {
+        if let (Some(cue), Some(modality)) = (settings.cue(alphabet.0), modalities.get(alphabet.0))
+        {
+            let symbols = modality.symbols(&settings);
+            text.sections[0].value = format!("{}/{}", cue.active_symbols(symbols).len(), symbols);
+        }
+    }
+}
+
+/// Lure rate of a modality.
+#[derive(Component)]
+pub struct LureText(pub ModalityId);
+
+pub fn lure_text_system(settings: Res<GameSettings>, mut query: Query<(&mut Text, &LureText)>) {
+    for (mut text, lure) in &mut query {
+        if let Some(cue) = settings.cue(lure.0) {
+            text.sections[0].value = lure_label(cue.lure_rate);
+        }
+    }
+}
+
+/// Seed of the next game, or the one being typed.
+#[derive(Component)]
+pub struct SeedText;
+
+pub fn seed_text_system(
+    settings: Res<GameSettings>,
+    entry: Res<SeedEntry>,
+    mut query: Query<&mut Text, With<SeedText>>,
+) {
+    for mut text in &mut query {
+        text.sections[0].value = match entry.0.as_ref() {
+            Some(digits) => format!("{}_", digits),
+            None => settings.seed.to_string(),
+        };
+    }
+}
+
+#[derive(Component)]
+pub struct OperationText;
+
+pub fn operation_text_system(
+    settings: Res<GameSettings>,
+    mut query: Query<&mut Text, With<OperationText>>,
+) {
+    for mut text in &mut query {
+        text.sections[0].value = settings.operation.sign().to_string();
+    }
+}
+
+#[derive(Component)]
+pub struct GridText;
+
+pub fn grid_text_system(settings: Res<GameSettings>, mut query: Query<&mut Text, With<GridText>>) {
+    for mut text in &mut query {
+        text.sections[0].value = settings.grid.label();
+    }
+}
+
+#[derive(Component)]
+pub struct ScaleText;
+
+pub fn scale_text_system(
+    settings: Res<GameSettings>,
+    mut query: Query<&mut Text, With<ScaleText>>,
+) {
+    for mut text in &mut query {
+        text.sections[0].value = settings.scale.label().to_string();
+    }
+}
+
+#[derive(Component)]
+pub struct StereoText;
+
+pub fn stereo_text_system(
+    settings: Res<GameSettings>,
+    mut query: Query<&mut Text, With<StereoText>>,
+) {
+    for mut text in &mut query {
+        text.sections[0].value = settings.stereo.label();
+    }
+}
+
+#[derive(Component)]
+pub struct TilesText;
+
+pub fn tiles_text_system(
+    settings: Res<GameSettings>,
+    mut query: Query<&mut Text, With<TilesText>>,
+) {
+    for mut text in &mut query {
+        text.sections[0].value = settings.tiles.to_string();
+    }
+}
+
+#[derive(Component)]
+pub struct StimulusText;
+
+pub fn stimulus_text_system(
+    settings: Res<GameSettings>,
+    mut query: Query<&mut Text, With<StimulusText>>,
+) {
+    for mut text in &mut query {
+        text.sections[0].value = time_label(settings.stimulus_time);
+    }
+}
+
+#[derive(Component)]
+pub struct IntervalText;
+
+pub fn interval_text_system(
+    settings: Res<GameSettings>,
+    mut query: Query<&mut Text, With<IntervalText>>,
+) {
+    for mut text in &mut query {
+        text.sections[0].value = time_label(settings.interval_time);
+    }
+}
+
+#[derive(Component)]
+pub struct JitterText;
+
+pub fn jitter_text_system(
+    settings: Res<GameSettings>,
+    mut query: Query<&mut Text, With<JitterText>>,
+) {
+    for mut text in &mut query {
+        text.sections[0].value = settings.jitter.label();
+    }
+}
 
 #[derive(Component)]
-pub struct NBackText;
+pub struct ProgressionText;
 
-pub fn nback_text_system(
+pub fn progression_text_system(
     settings: Res<GameSettings>,
-    mut query: Query<&mut Text, With<NBackText>>,
+    mut query: Query<&mut Text, With<ProgressionText>>,
 ) {
     for mut text in &mut query {
-        text.sections[0].value = format!("{}-Back", settings.n);
+        text.sections[0].value = settings.progression.label().to_string();
     }
 }
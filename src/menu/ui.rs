@@ -2,17 +2,32 @@ use bevy::{
     input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
 };
+use nback_core::cue::TargetOffset;
 
 use crate::{
-    game::{score::LatestGameScores, settings::GameSettings},
+    game::{
+        modality::{Modalities, ModalityInfo},
+        score::LatestGameScores,
+        settings::{lure_label, time_label, GameMode, GameSettings},
+    },
     palette,
     state::{AppState, OnMenuScreen},
 };
 
 use super::{
-    button::{self, DecreaseNButton, IncreaseNButton, PlayButton},
-    checkbox::{Checkbox, ColorCheckBox, PositionCheckBox, SoundCheckbox},
-    text::NBackText,
+    button::{
+        self, AlphabetButton, DecreaseNButton, GridButton, IncreaseNButton, IntervalButton,
+        JitterButton, LureButton, OperationButton, PlayButton, ProgressionButton, SavePlanButton,
+        ScaleButton, SeedButton, StereoButton, StimulusButton, TilesButton,
+    },
+    checkbox::{
+        Checkbox, CrabCheckbox, ModalityCheckbox, ModeCheckbox, PlanCheckbox, QuotaCheckbox,
+        VariableNCheckbox,
+    },
+    text::{
+        AlphabetText, GridText, IntervalText, JitterText, LureText, NBackText, OperationText,
+        ProgressionText, ScaleText, SeedText, StereoText, StimulusText, TilesText,
+    },
 };
 
 pub struct UiPlugin;
@@ -31,6 +46,7 @@ pub fn menu_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     settings: Res<GameSettings>,
+    modalities: Res<Modalities>,
     scores: ResMut<LatestGameScores>,
 ) {
     let font = asset_server.load("embedded://fonts/FiraSans-Bold.ttf");
@@ -68,6 +84,7 @@ pub fn menu_ui(
                 .spawn(NodeBundle {
                     style: Style {
                         flex_grow: 0.5,
+                        flex_direction: FlexDirection::Column,
                         justify_content: JustifyContent::Center,
                         align_items: AlignItems::Center,
                         margin: UiRect::all(Val::Px(5.0)),
@@ -78,7 +95,19 @@ pub fn menu_ui(
                     ..default()
                 })
                 .with_children(|parent| {
-                    select_n(parent, &settings, font.clone());
+                    for modality in modalities.iter().filter(|modality| modality.is_listed()) {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                select_n(parent, modality, &settings, font.clone());
+                            });
+                    }
                 });
 
             parent
@@ -91,11 +120,7 @@ pub fn menu_ui(
                             GridTrack::min_content(),
                             GridTrack::min_content(),
                         ],
-                        grid_template_rows: vec![
-                            GridTrack::min_content(),
-                            GridTrack::min_content(),
-                            GridTrack::min_content(),
-                        ],
+                        grid_auto_rows: vec![GridTrack::min_content()],
                         row_gap: Val::Px(12.0),
                         column_gap: Val::Px(12.0),
                         padding: UiRect::all(Val::Px(24.0)),
@@ -105,7 +130,7 @@ pub fn menu_ui(
                     ..default()
                 })
                 .with_children(|parent| {
-                    cue_selection(parent, &settings, font.clone());
+                    cue_selection(parent, &settings, &modalities, font.clone());
                 });
 
             parent
@@ -147,7 +172,7 @@ pub fn menu_ui(
                     ..default()
                 })
                 .with_children(|parent| {
-                    score_history(parent, &scores, font.clone());
+                    score_history(parent, &scores, &modalities, font.clone());
                 });
         });
 }
@@ -163,7 +188,27 @@ fn game_title(parent: &mut ChildBuilder, font: Handle<Font>) {
     ));
 }
 
-fn select_n(parent: &mut ChildBuilder, settings: &Res<GameSettings>, font: Handle<Font>) {
+fn select_n(
+    parent: &mut ChildBuilder,
+    modality: &ModalityInfo,
+    settings: &Res<GameSettings>,
+    font: Handle<Font>,
+) {
+    parent.spawn(TextBundle {
+        style: Style {
+            width: Val::Px(160.0),
+            ..default()
+        },
+        ..TextBundle::from_section(
+            modality.label,
+            TextStyle {
+                font: font.clone(),
+                font_size: 32.0,
+                color: Color::rgb(0.9, 0.9, 0.9),
+            },
+        )
+    });
+
     parent
         .spawn((
             ButtonBundle {
@@ -180,7 +225,7 @@ fn select_n(parent: &mut ChildBuilder, settings: &Res<GameSettings>, font: Handl
                 background_color: button::NORMAL_BUTTON.into(),
                 ..default()
             },
-            DecreaseNButton,
+            DecreaseNButton(modality.id),
         ))
         .with_children(|parent| {
             parent.spawn(TextBundle::from_section(
@@ -204,14 +249,16 @@ fn select_n(parent: &mut ChildBuilder, settings: &Res<GameSettings>, font: Handl
         .with_children(|parent| {
             parent.spawn((
                 TextBundle::from_section(
-                    settings.n.to_string(),
+                    settings
+                        .cue(modality.id)
+                        .map_or(String::new(), |cue| cue.n.to_string()),
                     TextStyle {
                         font: font.clone(),
                         font_size: 40.0,
                         color: Color::rgb(0.9, 0.9, 0.9),
                     },
                 ),
-                NBackText,
+                NBackText(modality.id),
             ));
         });
 
@@ -231,21 +278,134 @@ fn select_n(parent: &mut ChildBuilder, settings: &Res<GameSettings>, font: Handl
                 background_color: button::NORMAL_BUTTON.into(),
                 ..default()
             },
-            IncreaseNButton,
+            IncreaseNButton(modality.id),
         ))
         .with_children(|parent| {
             parent.spawn(TextBundle::from_section(
                 "+",
                 TextStyle {
-                    font,
+                    font: font.clone(),
                     font_size: 40.0,
                     color: Color::rgb(0.9, 0.9, 0.9),
                 },
             ));
         });
+
+    let symbols = modality.symbols(settings);
+    let size = settings
+        .cue(modality.id)
+        .map_or(symbols, |cue| cue.active_symbols(symbols).len());
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(64.0),
+                    height: Val::Px(40.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(5.0)),
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            AlphabetButton(modality.id),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    format!("{}/{}", size, symbols),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                AlphabetText(modality.id),
+            ));
+        });
+
+    let lure_rate = settings.cue(modality.id).map_or(0.0, |cue| cue.lure_rate);
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(64.0),
+                    height: Val::Px(40.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(5.0)),
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            LureButton(modality.id),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    lure_label(lure_rate),
+                    TextStyle {
+                        font,
+                        font_size: 24.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                LureText(modality.id),
+            ));
+        });
 }
 
-fn cue_selection(parent: &mut ChildBuilder, settings: &Res<GameSettings>, font: Handle<Font>) {
+fn cue_selection(
+    parent: &mut ChildBuilder,
+    settings: &Res<GameSettings>,
+    modalities: &Res<Modalities>,
+    font: Handle<Font>,
+) {
+    for modality in modalities.iter().filter(|modality| modality.is_listed()) {
+        let enabled = settings.cue(modality.id).is_some_and(|cue| cue.enabled);
+
+        parent.spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: if enabled {
+                    button::PRESSED_BUTTON.into()
+                } else {
+                    button::NORMAL_BUTTON.into()
+                },
+                ..default()
+            },
+            ModalityCheckbox(modality.id),
+            Checkbox { checked: enabled },
+        ));
+
+        parent.spawn(TextBundle::from_section(
+            modality.label,
+            TextStyle {
+                font: font.clone(),
+                font_size: 32.0,
+                color: Color::rgb(0.9, 0.9, 0.9),
+            },
+        ));
+    }
+
     parent.spawn((
         ButtonBundle {
             style: Style {
@@ -257,17 +417,21 @@ fn cue_selection(parent: &mut ChildBuilder, settings: &Res<GameSettings>, font:
                 ..default()
             },
             border_color: button::BUTTON_BORDER_COLOR.into(),
-            background_color: button::PRESSED_BUTTON.into(),
+            background_color: if settings.quota.is_some() {
+                button::PRESSED_BUTTON.into()
+            } else {
+                button::NORMAL_BUTTON.into()
+            },
             ..default()
         },
-        PositionCheckBox,
+        QuotaCheckbox,
         Checkbox {
-            checked: settings.position,
+            checked: settings.quota.is_some(),
         },
     ));
 
     parent.spawn(TextBundle::from_section(
-        "Position",
+        "Fixed matches",
         TextStyle {
             font: font.clone(),
             font_size: 32.0,
@@ -286,17 +450,54 @@ fn cue_selection(parent: &mut ChildBuilder, settings: &Res<GameSettings>, font:
                 ..default()
             },
             border_color: button::BUTTON_BORDER_COLOR.into(),
-            background_color: button::PRESSED_BUTTON.into(),
+            background_color: if settings.variable_n {
+                button::PRESSED_BUTTON.into()
+            } else {
+                button::NORMAL_BUTTON.into()
+            },
             ..default()
         },
-        SoundCheckbox,
+        VariableNCheckbox,
         Checkbox {
-            checked: settings.sound,
+            checked: settings.variable_n,
+        },
+    ));
+
+    parent.spawn(TextBundle::from_section(
+        "Variable N",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    let crab = settings.target == TargetOffset::Crab;
+
+    parent.spawn((
+        ButtonBundle {
+            style: Style {
+                width: Val::Px(32.0),
+                height: Val::Px(32.0),
+                border: UiRect::all(Val::Px(3.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            border_color: button::BUTTON_BORDER_COLOR.into(),
+            background_color: if crab {
+                button::PRESSED_BUTTON.into()
+            } else {
+                button::NORMAL_BUTTON.into()
+            },
+            ..default()
         },
+        CrabCheckbox,
+        Checkbox { checked: crab },
     ));
 
     parent.spawn(TextBundle::from_section(
-        "Sound",
+        "Crab-back",
         TextStyle {
             font: font.clone(),
             font_size: 32.0,
@@ -315,32 +516,67 @@ fn cue_selection(parent: &mut ChildBuilder, settings: &Res<GameSettings>, font:
                 ..default()
             },
             border_color: button::BUTTON_BORDER_COLOR.into(),
-            background_color: button::PRESSED_BUTTON.into(),
+            background_color: if settings.mode == GameMode::Combination {
+                button::PRESSED_BUTTON.into()
+            } else {
+                button::NORMAL_BUTTON.into()
+            },
             ..default()
         },
-        ColorCheckBox,
+        ModeCheckbox(GameMode::Combination),
         Checkbox {
-            checked: settings.color,
+            checked: settings.mode == GameMode::Combination,
         },
     ));
 
     parent.spawn(TextBundle::from_section(
-        "Color",
+        "Combination",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent.spawn((
+        ButtonBundle {
+            style: Style {
+                width: Val::Px(32.0),
+                height: Val::Px(32.0),
+                border: UiRect::all(Val::Px(3.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            border_color: button::BUTTON_BORDER_COLOR.into(),
+            background_color: if settings.mode == GameMode::Arithmetic {
+                button::PRESSED_BUTTON.into()
+            } else {
+                button::NORMAL_BUTTON.into()
+            },
+            ..default()
+        },
+        ModeCheckbox(GameMode::Arithmetic),
+        Checkbox {
+            checked: settings.mode == GameMode::Arithmetic,
+        },
+    ));
+
+    parent.spawn(TextBundle::from_section(
+        "Arithmetic",
         TextStyle {
             font: font.clone(),
             font_size: 32.0,
             color: Color::rgb(0.9, 0.9, 0.9),
         },
     ));
-}
 
-fn play_button(parent: &mut ChildBuilder, font: Handle<Font>) {
     parent
         .spawn((
             ButtonBundle {
                 style: Style {
-                    width: Val::Px(150.0),
-                    height: Val::Px(65.0),
+                    width: Val::Px(32.0),
+                    height: Val::Px(32.0),
                     border: UiRect::all(Val::Px(3.0)),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
@@ -350,23 +586,24 @@ fn play_button(parent: &mut ChildBuilder, font: Handle<Font>) {
                 background_color: button::NORMAL_BUTTON.into(),
                 ..default()
             },
-            PlayButton,
+            OperationButton,
         ))
         .with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                "PLAY",
-                TextStyle {
-                    font: font.clone(),
-                    font_size: 32.0,
-                    color: Color::rgb(0.9, 0.9, 0.9),
-                },
+            parent.spawn((
+                TextBundle::from_section(
+                    settings.operation.sign(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                OperationText,
             ));
         });
-}
 
-fn score_history(parent: &mut ChildBuilder, scores: &ResMut<LatestGameScores>, font: Handle<Font>) {
     parent.spawn(TextBundle::from_section(
-        "N-Back",
+        "Operation",
         TextStyle {
             font: font.clone(),
             font_size: 32.0,
@@ -374,8 +611,40 @@ fn score_history(parent: &mut ChildBuilder, scores: &ResMut<LatestGameScores>, f
         },
     ));
 
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            GridButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    settings.grid.label(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                GridText,
+            ));
+        });
+
     parent.spawn(TextBundle::from_section(
-        "Time",
+        "Grid",
         TextStyle {
             font: font.clone(),
             font_size: 32.0,
@@ -383,8 +652,40 @@ fn score_history(parent: &mut ChildBuilder, scores: &ResMut<LatestGameScores>, f
         },
     ));
 
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            ScaleButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    settings.scale.label(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                ScaleText,
+            ));
+        });
+
     parent.spawn(TextBundle::from_section(
-        "Score",
+        "Scale",
         TextStyle {
             font: font.clone(),
             font_size: 32.0,
@@ -392,33 +693,461 @@ fn score_history(parent: &mut ChildBuilder, scores: &ResMut<LatestGameScores>, f
         },
     ));
 
-    for score in scores.0.iter() {
-        parent.spawn(TextBundle::from_section(
-            format!("{}", score.n),
-            TextStyle {
-                font: font.clone(),
-                font_size: 24.0,
-                color: Color::rgb(0.9, 0.9, 0.9),
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
             },
-        ));
+            StereoButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    settings.stereo.label(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                StereoText,
+            ));
+        });
 
-        parent.spawn(TextBundle::from_section(
-            format!("{:.2}s", score.total_rounds as f32 * score.round_duration),
-            TextStyle {
-                font: font.clone(),
-                font_size: 24.0,
-                color: Color::rgb(0.9, 0.9, 0.9),
+    parent.spawn(TextBundle::from_section(
+        "Stereo",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            StimulusButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    time_label(settings.stimulus_time),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                StimulusText,
+            ));
+        });
+
+    parent.spawn(TextBundle::from_section(
+        "Stimulus",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            IntervalButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    time_label(settings.interval_time),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                IntervalText,
+            ));
+        });
+
+    parent.spawn(TextBundle::from_section(
+        "Interval",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            JitterButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    settings.jitter.label(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                JitterText,
+            ));
+        });
+
+    parent.spawn(TextBundle::from_section(
+        "Jitter",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            ProgressionButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    settings.progression.label(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                ProgressionText,
+            ));
+        });
+
+    parent.spawn(TextBundle::from_section(
+        "Progression",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            SeedButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    settings.seed.to_string(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                SeedText,
+            ));
+        });
+
+    parent.spawn(TextBundle::from_section(
+        "Seed",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            TilesButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    settings.tiles.to_string(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                ),
+                TilesText,
+            ));
+        });
+
+    parent.spawn(TextBundle::from_section(
+        "Tiles",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent.spawn((
+        ButtonBundle {
+            style: Style {
+                width: Val::Px(32.0),
+                height: Val::Px(32.0),
+                border: UiRect::all(Val::Px(3.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            border_color: button::BUTTON_BORDER_COLOR.into(),
+            background_color: button::NORMAL_BUTTON.into(),
+            ..default()
+        },
+        SavePlanButton,
+    ));
+
+    parent.spawn(TextBundle::from_section(
+        "Save plan",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    let planned = settings.plan.is_some();
+
+    parent.spawn((
+        ButtonBundle {
+            style: Style {
+                width: Val::Px(32.0),
+                height: Val::Px(32.0),
+                border: UiRect::all(Val::Px(3.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            border_color: button::BUTTON_BORDER_COLOR.into(),
+            background_color: if planned {
+                button::PRESSED_BUTTON.into()
+            } else {
+                button::NORMAL_BUTTON.into()
+            },
+            ..default()
+        },
+        PlanCheckbox,
+        Checkbox { checked: planned },
+    ));
+
+    parent.spawn(TextBundle::from_section(
+        "Load plan",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+}
+
+fn play_button(parent: &mut ChildBuilder, font: Handle<Font>) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(150.0),
+                    height: Val::Px(65.0),
+                    border: UiRect::all(Val::Px(3.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: button::BUTTON_BORDER_COLOR.into(),
+                background_color: button::NORMAL_BUTTON.into(),
+                ..default()
+            },
+            PlayButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "PLAY",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 32.0,
+                    color: Color::rgb(0.9, 0.9, 0.9),
+                },
+            ));
+        });
+}
+
+fn score_history(
+    parent: &mut ChildBuilder,
+    scores: &ResMut<LatestGameScores>,
+    modalities: &Modalities,
+    font: Handle<Font>,
+) {
+    parent.spawn(TextBundle::from_section(
+        "N-Back",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent.spawn(TextBundle::from_section(
+        "Time",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    parent.spawn(TextBundle::from_section(
+        "Score",
+        TextStyle {
+            font: font.clone(),
+            font_size: 32.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        },
+    ));
+
+    for score in scores.0.iter() {
+        parent.spawn(TextBundle::from_section(
+            score.levels_label(modalities),
+            TextStyle {
+                font: font.clone(),
+                font_size: 24.0,
+                color: Color::rgb(0.9, 0.9, 0.9),
             },
         ));
 
         parent.spawn(TextBundle::from_section(
-            format!("{}%", score.f1_score_percent),
+            format!("{:.2}s", score.total_rounds as f32 * score.round_duration),
             TextStyle {
                 font: font.clone(),
                 font_size: 24.0,
                 color: Color::rgb(0.9, 0.9, 0.9),
             },
         ));
+
+        parent.spawn(TextBundle::from_sections([
+            TextSection::new(
+                format!("{}%", score.score_percent()),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 24.0,
+                    color: Color::rgb(0.9, 0.9, 0.9),
+                },
+            ),
+            TextSection::new(
+                [
+                    score.level_change_label(),
+                    score.detection_label(),
+                    score.latency_label(),
+                    score.modalities_label(modalities),
+                    score.seed_label(),
+                ]
+                .iter()
+                .filter(|label| !label.is_empty())
+                .fold(String::new(), |text, label| text + "\n" + label),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 16.0,
+                    color: Color::rgb(0.9, 0.9, 0.9),
+                },
+            ),
+        ]));
     }
 }
 
@@ -6,6 +6,8 @@ pub enum AppState {
     AssetLoading,
     Menu,
     Game,
+    /// Passed through to leave the game and start it over.
+    Restarting,
 }
 
 /// Tag component used to tag entities added on the splash screen